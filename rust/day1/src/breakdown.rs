@@ -0,0 +1,131 @@
+use std::fmt::Write;
+
+use crate::{calibration::lines, CalibrationError, Calibrator, Token, TokenKind};
+
+const FIRST: &str = "\x1b[32m";
+const LAST: &str = "\x1b[34m";
+const BOTH: &str = "\x1b[33m";
+const WORD: &str = "\x1b[4m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineBreakdown {
+    pub line: usize,
+    pub offset: usize,
+    pub text: String,
+    pub first: Token,
+    pub last: Token,
+    pub value: u128,
+}
+
+impl LineBreakdown {
+    fn style(&self, i: usize) -> Option<String> {
+        let (token, color) = match (
+            self.first.span().contains(&i),
+            self.last.span().contains(&i),
+        ) {
+            (true, true) => (self.first, BOTH),
+            (true, false) => (self.first, FIRST),
+            (false, true) => (self.last, LAST),
+            (false, false) => return None,
+        };
+        match token.kind {
+            TokenKind::Digit => Some(color.to_owned()),
+            TokenKind::Word => Some(format!("{color}{WORD}")),
+        }
+    }
+
+    // The first token is green, the last one blue and bytes shared by both
+    // (a single token, or overlaps like "oneight") yellow. Words are
+    // underlined.
+    pub fn render(&self) -> String {
+        let mut cuts = vec![
+            0,
+            self.first.start,
+            self.first.end,
+            self.last.start,
+            self.last.end,
+            self.text.len(),
+        ];
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut out = format!("{:>5} | ", self.line);
+        for cut in cuts.windows(2) {
+            let segment = &self.text[cut[0]..cut[1]];
+            match self.style(cut[0]) {
+                Some(style) => write!(out, "{style}{segment}{RESET}").unwrap(),
+                None => out.push_str(segment),
+            }
+        }
+        write!(out, " => {}", self.value).unwrap();
+        out
+    }
+}
+
+impl Calibrator {
+    pub fn breakdown(&self, s: &str) -> Vec<Result<LineBreakdown, CalibrationError>> {
+        let mut tokens = Vec::new();
+        lines(s.as_bytes(), 1, 0)
+            .map(|(line, offset, text)| {
+                self.scan(text, &mut tokens);
+                let value = self.evaluate(&tokens).map_err(|kind| CalibrationError {
+                    line,
+                    offset,
+                    kind,
+                })?;
+                Ok(LineBreakdown {
+                    line,
+                    offset,
+                    text: String::from_utf8_lossy(text)
+                        .trim_end_matches(['\n', '\r'])
+                        .to_owned(),
+                    first: tokens[0],
+                    last: tokens[tokens.len() - 1],
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    // Lines that both calibrators read, but to different values.
+    pub fn differences(&self, other: &Calibrator, s: &str) -> Vec<(LineBreakdown, LineBreakdown)> {
+        self.breakdown(s)
+            .into_iter()
+            .zip(other.breakdown(s))
+            .filter_map(|pair| match pair {
+                (Ok(ours), Ok(theirs)) if ours.value != theirs.value => Some((ours, theirs)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_breakdown() {
+    let input = "two1nine\n7pqrstsixteen\nzoneight";
+    let part1 = Calibrator::digits();
+    let part2 = Calibrator::english();
+    let breakdown = part2.breakdown(input);
+    let second = breakdown[1].as_ref().unwrap();
+    assert_eq!(
+        (second.first.span(), second.first.kind),
+        (0..1, TokenKind::Digit)
+    );
+    assert_eq!(
+        (second.last.span(), second.last.kind),
+        (6..9, TokenKind::Word)
+    );
+    assert_eq!(second.value, 76);
+    assert_eq!(
+        breakdown[2].as_ref().unwrap().render(),
+        "    3 | z\x1b[32m\x1b[4mon\x1b[0m\x1b[33m\x1b[4me\x1b[0m\x1b[34m\x1b[4might\x1b[0m => 18"
+    );
+    let differences = part1.differences(&part2, input);
+    assert_eq!(
+        differences
+            .iter()
+            .map(|(ours, theirs)| (ours.line, ours.value, theirs.value))
+            .collect::<Vec<_>>(),
+        vec![(1, 11, 29), (2, 77, 76)]
+    );
+}
@@ -0,0 +1,345 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead},
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+use crate::{DigitScanner, Granularity, Policy, Token, Vocabulary};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    NoDigit,
+    Overflow,
+    // The policy can not produce a value from any line, e.g. FirstLastK(0).
+    InvalidPolicy,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::NoDigit => write!(f, "no digit found"),
+            ErrorKind::Overflow => write!(f, "calibration value does not fit into 128 bits"),
+            ErrorKind::InvalidPolicy => write!(f, "the policy takes no digits"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorKind {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalibrationError {
+    pub line: usize,
+    pub offset: usize,
+    pub kind: ErrorKind,
+}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} (byte {}): {}",
+            self.line, self.offset, self.kind
+        )
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CalibrationReport {
+    pub total: u128,
+    pub errors: Vec<CalibrationError>,
+}
+
+// Yields (line number, byte offset, line) for every non-blank line, counting
+// from the given position. Line numbers are 1-based and count blank lines, so
+// they match an editor.
+pub(crate) fn lines(
+    bytes: &[u8],
+    line: usize,
+    offset: usize,
+) -> impl Iterator<Item = (usize, usize, &[u8])> {
+    bytes
+        .split_inclusive(|&byte| byte == b'\n')
+        .scan(offset, |offset, text| {
+            let start = *offset;
+            *offset += text.len();
+            Some((start, text))
+        })
+        .zip(line..)
+        .map(|((offset, text), line)| (line, offset, text))
+        .filter(|(_, _, text)| !is_blank(text))
+}
+
+fn is_blank(text: &[u8]) -> bool {
+    text.iter().all(u8::is_ascii_whitespace)
+}
+
+struct Chunk {
+    bytes: Vec<u8>,
+    line: usize,
+    offset: usize,
+}
+
+fn send_chunks<R: BufRead>(
+    reader: &mut R,
+    sender: &mpsc::SyncSender<Chunk>,
+    chunk_size: usize,
+) -> io::Result<()> {
+    let (mut line, mut offset) = (1, 0);
+    loop {
+        let mut bytes = Vec::with_capacity(chunk_size);
+        while bytes.len() < chunk_size && reader.read_until(b'\n', &mut bytes)? > 0 {}
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let next_line = line + bytes.iter().filter(|&&byte| byte == b'\n').count();
+        let next_offset = offset + bytes.len();
+        if sender
+            .send(Chunk {
+                bytes,
+                line,
+                offset,
+            })
+            .is_err()
+        {
+            return Ok(());
+        }
+        (line, offset) = (next_line, next_offset);
+    }
+}
+
+pub struct Calibrator {
+    scanner: DigitScanner,
+    granularity: Granularity,
+    policy: Policy,
+}
+
+impl Calibrator {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        Self {
+            scanner: DigitScanner::new(vocabulary),
+            granularity: Granularity::default(),
+            policy: Policy::default(),
+        }
+    }
+
+    pub fn with_granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    // Fails with `ErrorKind::InvalidPolicy` for policies that take no digits,
+    // instead of failing on every line later.
+    pub fn with_policy(mut self, policy: Policy) -> Result<Self, ErrorKind> {
+        if !policy.is_valid() {
+            return Err(ErrorKind::InvalidPolicy);
+        }
+        self.policy = policy;
+        Ok(self)
+    }
+
+    pub fn unicode(vocabulary: &Vocabulary) -> Self {
+        Self {
+            scanner: DigitScanner::unicode(vocabulary),
+            granularity: Granularity::default(),
+            policy: Policy::default(),
+        }
+    }
+
+    pub fn digits() -> Self {
+        Self::new(&Vocabulary::default())
+    }
+
+    pub fn english() -> Self {
+        Self::new(&Vocabulary::english())
+    }
+
+    pub(crate) fn scan(&self, line: &[u8], tokens: &mut Vec<Token>) {
+        self.scanner.tokens(line, tokens);
+    }
+
+    pub(crate) fn evaluate(&self, tokens: &[Token]) -> Result<u128, ErrorKind> {
+        self.policy.apply(tokens, self.granularity)
+    }
+
+    pub fn value(&self, line: &[u8], tokens: &mut Vec<Token>) -> Result<u128, ErrorKind> {
+        self.scan(line, tokens);
+        self.evaluate(tokens)
+    }
+
+    pub fn try_sum(&self, s: &str) -> Result<u128, CalibrationError> {
+        let mut tokens = Vec::new();
+        lines(s.as_bytes(), 1, 0)
+            .map(|(line, offset, text)| {
+                self.value(text, &mut tokens)
+                    .map_err(|kind| CalibrationError { line, offset, kind })
+            })
+            .sum()
+    }
+
+    pub fn lenient_sum(&self, s: &str) -> CalibrationReport {
+        let mut report = CalibrationReport::default();
+        self.add_chunk(s.as_bytes(), 1, 0, &mut Vec::new(), &mut report);
+        report
+    }
+
+    fn add_line(
+        &self,
+        text: &[u8],
+        line: usize,
+        offset: usize,
+        tokens: &mut Vec<Token>,
+        report: &mut CalibrationReport,
+    ) {
+        match self.value(text, tokens) {
+            Ok(value) => report.total += value,
+            Err(kind) => report.errors.push(CalibrationError { line, offset, kind }),
+        }
+    }
+
+    fn add_chunk(
+        &self,
+        bytes: &[u8],
+        line: usize,
+        offset: usize,
+        tokens: &mut Vec<Token>,
+        report: &mut CalibrationReport,
+    ) {
+        for (line, offset, text) in lines(bytes, line, offset) {
+            self.add_line(text, line, offset, tokens, report);
+        }
+    }
+
+    // Sums the document one line at a time, so memory use does not depend on
+    // the size of the input.
+    pub fn sum_reader<R: BufRead>(&self, mut reader: R) -> io::Result<CalibrationReport> {
+        let mut report = CalibrationReport::default();
+        let mut tokens = Vec::new();
+        let mut buffer = Vec::new();
+        let (mut line, mut offset) = (1, 0);
+        loop {
+            buffer.clear();
+            let read = reader.read_until(b'\n', &mut buffer)?;
+            if read == 0 {
+                break Ok(report);
+            }
+            if !is_blank(&buffer) {
+                self.add_line(&buffer, line, offset, &mut tokens, &mut report);
+            }
+            line += 1;
+            offset += read;
+        }
+    }
+
+    // Cuts the input into chunks of roughly `chunk_size` bytes at line
+    // boundaries and sums them on `threads` workers. At most `threads` chunks
+    // wait in the queue, which bounds the memory use.
+    pub fn sum_reader_parallel<R: BufRead>(
+        &self,
+        mut reader: R,
+        threads: usize,
+        chunk_size: usize,
+    ) -> io::Result<CalibrationReport> {
+        let threads = threads.max(1);
+        let (sender, receiver) = mpsc::sync_channel::<Chunk>(threads);
+        let receiver = Mutex::new(receiver);
+        let (read, reports) = thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut report = CalibrationReport::default();
+                        let mut tokens = Vec::new();
+                        loop {
+                            let chunk = receiver.lock().unwrap().recv();
+                            let Ok(chunk) = chunk else {
+                                break report;
+                            };
+                            self.add_chunk(
+                                &chunk.bytes,
+                                chunk.line,
+                                chunk.offset,
+                                &mut tokens,
+                                &mut report,
+                            );
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            let read = send_chunks(&mut reader, &sender, chunk_size.max(1));
+            drop(sender);
+            let reports = workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect::<Vec<_>>();
+            (read, reports)
+        });
+        read?;
+        let mut report = CalibrationReport::default();
+        for partial in reports {
+            report.total += partial.total;
+            report.errors.extend(partial.errors);
+        }
+        report.errors.sort_by_key(|error| error.line);
+        Ok(report)
+    }
+}
+
+#[test]
+fn test_bad_lines() {
+    let input = "1abc2\n\nno digits here\r\ntreb7uchet\nxyz";
+    let calibrator = Calibrator::digits();
+    assert_eq!(
+        calibrator.try_sum(input),
+        Err(CalibrationError {
+            line: 3,
+            offset: 7,
+            kind: ErrorKind::NoDigit
+        })
+    );
+    let report = calibrator.lenient_sum(input);
+    assert_eq!(report.total, 12 + 77);
+    assert_eq!(
+        report.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+        vec![3, 5]
+    );
+    assert_eq!(
+        report.errors[1].to_string(),
+        "line 5 (byte 34): no digit found"
+    );
+}
+
+#[test]
+fn test_streaming() {
+    let input = "two1nine\neightwothree\nabcone2threexyz\n\nxtwone3four\nnothing\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+    let calibrator = Calibrator::english();
+    let expected = calibrator.lenient_sum(input);
+    assert_eq!(expected.total, 281);
+    assert_eq!(calibrator.sum_reader(input.as_bytes()).unwrap(), expected);
+    for chunk_size in [1, 7, 30, 1000] {
+        let report = calibrator
+            .sum_reader_parallel(input.as_bytes(), 3, chunk_size)
+            .unwrap();
+        assert_eq!(report, expected);
+    }
+}
+
+#[test]
+fn test_compound_numbers() {
+    let input = "eleven3twenty-one\nxninetynine\nseventeen4twentyoneight";
+    let calibrator = Calibrator::new(&Vocabulary::english_compound());
+    assert_eq!(calibrator.try_sum(input), Ok(11 + 99 + 18));
+    let calibrator = calibrator.with_granularity(Granularity::Numbers);
+    assert_eq!(calibrator.try_sum(input), Ok(1121 + 9999 + 178));
+}
+
+#[test]
+fn test_unicode_digits() {
+    let input = "TWO٣x\nNine and ۸\na９b";
+    assert_eq!(Calibrator::english().lenient_sum(input).total, 0);
+    assert_eq!(
+        Calibrator::unicode(&Vocabulary::english()).try_sum(input),
+        Ok(23 + 98 + 99)
+    );
+}
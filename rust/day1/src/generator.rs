@@ -0,0 +1,271 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+const WORDS: [&str; 10] = [
+    "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Letters that appear in none of the number words, so noise can never complete
+// or extend a word.
+const NOISE: &[u8] = b"abcdjklmpqyz";
+
+// Words that share their first letter with the last letter of another word,
+// e.g. "twone" or "oneight".
+const OVERLAPS: [(usize, usize); 8] = [
+    (1, 8),
+    (2, 1),
+    (3, 8),
+    (5, 8),
+    (7, 9),
+    (8, 2),
+    (8, 3),
+    (9, 8),
+];
+
+#[derive(Debug)]
+pub enum GeneratorError {
+    Infeasible(String),
+    Io(io::Error),
+}
+
+impl Display for GeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorError::Infeasible(reason) => write!(f, "infeasible document: {reason}"),
+            GeneratorError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+impl From<io::Error> for GeneratorError {
+    fn from(err: io::Error) -> Self {
+        GeneratorError::Io(err)
+    }
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn between(&mut self, low: u128, high: u128) -> u128 {
+        let random = (u128::from(self.next()) << 64) | u128::from(self.next());
+        low + random % (high - low + 1)
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+// Every generated line has a calibration value between 11 and 99 without a
+// zero digit, in part 1 as well as in part 2.
+pub struct Generator {
+    seed: u64,
+    lines: usize,
+    part1: u128,
+    part2: u128,
+    overlaps: bool,
+    differing: bool,
+}
+
+impl Generator {
+    pub fn new(seed: u64, lines: usize, part1: u128, part2: u128) -> Self {
+        Self {
+            seed,
+            lines,
+            part1,
+            part2,
+            overlaps: false,
+            differing: true,
+        }
+    }
+
+    // Decorates spelled numbers with overlapping words like "twone".
+    pub fn with_overlaps(mut self, overlaps: bool) -> Self {
+        self.overlaps = overlaps;
+        self
+    }
+
+    // Without differing lines, every line has the same value in both parts.
+    pub fn with_differing(mut self, differing: bool) -> Self {
+        self.differing = differing;
+        self
+    }
+
+    pub fn generate(&self) -> Result<String, GeneratorError> {
+        let mut out = Vec::new();
+        self.write_to(&mut out)?;
+        Ok(String::from_utf8(out).expect("generated documents are ASCII"))
+    }
+
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), GeneratorError> {
+        if !self.differing && self.part1 != self.part2 {
+            return Err(GeneratorError::Infeasible(
+                "part 1 and part 2 sums differ, but differing lines are disabled".to_owned(),
+            ));
+        }
+        let mut rng = SplitMix64(self.seed);
+        let (first1, last1) = self.digits(&mut rng, self.part1)?;
+        let (first2, last2) = if self.differing {
+            self.digits(&mut rng, self.part2)?
+        } else {
+            (first1.clone(), last1.clone())
+        };
+        let mut writer = io::BufWriter::new(writer);
+        let mut line = Vec::new();
+        for i in 0..self.lines {
+            line.clear();
+            self.line(
+                &mut rng,
+                &mut line,
+                (first1[i], last1[i]),
+                (first2[i], last2[i]),
+            );
+            line.push(b'\n');
+            writer.write_all(&line)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    // Splits `sum` into per-line tens and units digits between 1 and 9.
+    fn digits(
+        &self,
+        rng: &mut SplitMix64,
+        sum: u128,
+    ) -> Result<(Vec<u8>, Vec<u8>), GeneratorError> {
+        let lines = self.lines as u128;
+        let (low, high) = (lines, 9 * lines);
+        let tens_low = low.max(sum.saturating_sub(high).div_ceil(10));
+        let tens_high = high.min(sum.saturating_sub(low) / 10);
+        if tens_low > tens_high {
+            return Err(GeneratorError::Infeasible(format!(
+                "{sum} is not a sum of {} values between 11 and 99 without zero digits",
+                self.lines
+            )));
+        }
+        let tens = rng.between(tens_low, tens_high);
+        Ok((
+            distribute(rng, tens, self.lines),
+            distribute(rng, sum - 10 * tens, self.lines),
+        ))
+    }
+
+    fn line(&self, rng: &mut SplitMix64, line: &mut Vec<u8>, part1: (u8, u8), part2: (u8, u8)) {
+        let ((first1, last1), (first2, last2)) = (part1, part2);
+        noise(rng, line);
+        if first2 != first1 || (self.overlaps && rng.chance(50)) {
+            self.word(rng, line, usize::from(first2), true);
+        }
+        line.push(b'0' + first1);
+        for _ in 0..rng.below(4) {
+            noise(rng, line);
+            if rng.chance(50) {
+                line.push(b'1' + rng.below(9) as u8);
+            } else {
+                line.extend_from_slice(WORDS[1 + rng.below(9)].as_bytes());
+            }
+        }
+        noise(rng, line);
+        line.push(b'0' + last1);
+        if last2 != last1 || (self.overlaps && rng.chance(50)) {
+            self.word(rng, line, usize::from(last2), false);
+        }
+        noise(rng, line);
+    }
+
+    // Writes the word for `digit`. With overlaps enabled, a leading word also
+    // runs into a following word and a trailing word is run into by a
+    // preceding one, which does not change the first and last number.
+    fn word(&self, rng: &mut SplitMix64, line: &mut Vec<u8>, digit: usize, leading: bool) {
+        let candidates = OVERLAPS
+            .iter()
+            .filter(|&&(first, last)| {
+                if leading {
+                    first == digit
+                } else {
+                    last == digit
+                }
+            })
+            .collect::<Vec<_>>();
+        if !self.overlaps || candidates.is_empty() {
+            line.extend_from_slice(WORDS[digit].as_bytes());
+            return;
+        }
+        let &(first, last) = candidates[rng.below(candidates.len())];
+        line.extend_from_slice(WORDS[first].as_bytes());
+        line.extend_from_slice(&WORDS[last].as_bytes()[1..]);
+    }
+}
+
+fn noise(rng: &mut SplitMix64, line: &mut Vec<u8>) {
+    for _ in 0..rng.below(5) {
+        line.push(NOISE[rng.below(NOISE.len())]);
+    }
+}
+
+// Random digits between 1 and 9 that add up to `sum`.
+fn distribute(rng: &mut SplitMix64, sum: u128, count: usize) -> Vec<u8> {
+    let mut remaining = sum - count as u128;
+    let mut digits = (0..count)
+        .map(|i| {
+            let slots_after = (count - i - 1) as u128;
+            let low = remaining.saturating_sub(8 * slots_after);
+            let extra = rng.between(low, remaining.min(8));
+            remaining -= extra;
+            1 + extra as u8
+        })
+        .collect::<Vec<_>>();
+    for i in (1..digits.len()).rev() {
+        digits.swap(i, rng.below(i + 1));
+    }
+    digits
+}
+
+#[test]
+fn test_generator() {
+    use crate::Calibrator;
+
+    let generator = Generator::new(7, 500, 27_000, 31_234).with_overlaps(true);
+    let document = generator.generate().unwrap();
+    assert_eq!(document.lines().count(), 500);
+    assert_eq!(document, generator.generate().unwrap());
+    assert_ne!(
+        document,
+        Generator::new(8, 500, 27_000, 31_234).generate().unwrap()
+    );
+    assert_eq!(Calibrator::digits().try_sum(&document), Ok(27_000));
+    assert_eq!(Calibrator::english().try_sum(&document), Ok(31_234));
+    assert!(document.contains("eightwo") || document.contains("oneight"));
+
+    let document = Generator::new(1, 100, 4_000, 4_000)
+        .with_overlaps(true)
+        .with_differing(false)
+        .generate()
+        .unwrap();
+    let differences = Calibrator::digits().differences(&Calibrator::english(), &document);
+    assert!(differences.is_empty());
+    assert_eq!(Calibrator::english().try_sum(&document), Ok(4_000));
+
+    assert!(Generator::new(1, 10, 100, 500).generate().is_err());
+    assert!(Generator::new(1, 10, 500, 990).generate().is_ok());
+    assert!(Generator::new(1, 10, 500, 600)
+        .with_differing(false)
+        .generate()
+        .is_err());
+}
@@ -1,48 +1,59 @@
 use aoc_traits::AdventOfCodeDay;
 
-fn parse_number_1(s: &str) -> u128 {
-    let asciis = s
-        .to_owned()
-        .chars()
-        .filter(|c| c.is_ascii_digit())
-        .collect::<String>();
-    let mut result_string = String::new();
-    result_string.push(asciis.chars().next().unwrap());
-    result_string.push(asciis.chars().next_back().unwrap());
-    result_string.parse().unwrap()
-}
+mod breakdown;
+mod calibration;
+mod generator;
+mod policy;
+mod scanner;
+mod unicode;
+mod vocabulary;
 
-fn parse_number_2(s: &str) -> u128 {
-    let asciis = s
-        .to_owned()
-        .replace("one", "one1one")
-        .replace("two", "two2two")
-        .replace("three", "three3three")
-        .replace("four", "four4four")
-        .replace("five", "five5five")
-        .replace("six", "six6six")
-        .replace("seven", "seven7seven")
-        .replace("eight", "eight8eight")
-        .replace("nine", "nine9nine")
-        .chars()
-        .filter(|c| c.is_ascii_digit())
-        .collect::<String>();
-    let mut result_string = String::new();
-    result_string.push(asciis.chars().next().unwrap());
-    result_string.push(asciis.chars().next_back().unwrap());
-    result_string.parse().unwrap()
-}
+pub use breakdown::LineBreakdown;
+pub use calibration::{CalibrationError, CalibrationReport, Calibrator, ErrorKind};
+pub use generator::{Generator, GeneratorError};
+pub use policy::{Granularity, Policy};
+pub use scanner::{DigitScanner, Token, TokenKind};
+pub use vocabulary::Vocabulary;
 
 fn day1_challenge1(s: &str) -> u128 {
-    s.trim().to_owned().lines().map(parse_number_1).sum()
+    Calibrator::digits().lenient_sum(s).total
 }
 
 fn day1_challenge2(s: &str) -> u128 {
-    s.trim().to_owned().lines().map(parse_number_2).sum()
+    Calibrator::english().lenient_sum(s).total
 }
 
 pub struct Day1Solver;
 
+impl Day1Solver {
+    pub fn solve_with(input: &str, vocabulary: &Vocabulary) -> u128 {
+        Self::solve_report(input, vocabulary).total
+    }
+
+    pub fn solve_with_policy(
+        input: &str,
+        vocabulary: &Vocabulary,
+        policy: Policy,
+    ) -> Result<u128, ErrorKind> {
+        Ok(Self::solve_report_with_policy(input, vocabulary, policy)?.total)
+    }
+
+    // Like `solve_with`, but also returns the lines that were skipped.
+    pub fn solve_report(input: &str, vocabulary: &Vocabulary) -> CalibrationReport {
+        Calibrator::new(vocabulary).lenient_sum(input)
+    }
+
+    pub fn solve_report_with_policy(
+        input: &str,
+        vocabulary: &Vocabulary,
+        policy: Policy,
+    ) -> Result<CalibrationReport, ErrorKind> {
+        Ok(Calibrator::new(vocabulary)
+            .with_policy(policy)?
+            .lenient_sum(input))
+    }
+}
+
 impl<'a> AdventOfCodeDay<'a> for Day1Solver {
     type ParsedInput = &'a str;
 
@@ -84,6 +95,58 @@ fn test_example_2() {
     assert_eq!(day1_challenge2(input), 281);
 }
 
+#[test]
+fn test_vocabularies() {
+    let input = "zweiundvierzig
+    sieben8fünf
+    un2trois";
+    assert_eq!(
+        Day1Solver::solve_with(input, &Vocabulary::german()),
+        24 + 75 + 22
+    );
+    assert_eq!(
+        Day1Solver::solve_with(input, &Vocabulary::french()),
+        11 + 88 + 13
+    );
+    let custom = Vocabulary::english().merge(Vocabulary::from_iter([("zwo", 2), ("neun", 9)]));
+    assert_eq!(Day1Solver::solve_with("onezwo\n3neun", &custom), 12 + 39);
+}
+
+#[test]
+fn test_report() {
+    let report = Day1Solver::solve_report("two1nine\nnothing\n\nabc", &Vocabulary::english());
+    assert_eq!(report.total, 29);
+    assert_eq!(
+        report
+            .errors
+            .iter()
+            .map(|error| (error.line, error.kind))
+            .collect::<Vec<_>>(),
+        vec![(2, ErrorKind::NoDigit), (4, ErrorKind::NoDigit)]
+    );
+}
+
+#[test]
+fn test_policy() {
+    let input = "1abc2
+    pqr3stu8vwx
+    a1b2c3d4e5f
+    treb7uchet";
+    let vocabulary = Vocabulary::default();
+    assert_eq!(
+        Day1Solver::solve_with_policy(input, &vocabulary, Policy::FirstLast),
+        Ok(142)
+    );
+    assert_eq!(
+        Day1Solver::solve_with_policy(input, &vocabulary, Policy::Sum),
+        Ok(3 + 11 + 15 + 7)
+    );
+    assert_eq!(
+        Day1Solver::solve_report_with_policy("123\n45", &vocabulary, Policy::FirstLastK(0)),
+        Err(ErrorKind::InvalidPolicy)
+    );
+}
+
 #[test]
 fn my_challenge() {
     let input = "ckmb52fldxkseven3fkjgcbzmnr7
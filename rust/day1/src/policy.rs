@@ -0,0 +1,148 @@
+use crate::{ErrorKind, Token};
+
+// Whether a calibration value is built from single digits, so "twenty-one"
+// contributes a 2 and a 1, or from whole numbers, so it contributes 21.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Granularity {
+    #[default]
+    Digits,
+    Numbers,
+}
+
+// How the digits (or numbers) of a line are turned into its calibration value.
+// `FirstLastK(k)` concatenates the first k and the last k digits. They overlap
+// on lines with fewer than 2k digits, so "123" gives 1223 for k = 2.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    #[default]
+    FirstLast,
+    Concatenate,
+    MinMax,
+    FirstLastK(usize),
+    Sum,
+}
+
+// Splits a token value into its decimal digits, or yields it whole.
+#[derive(Clone)]
+struct Parts {
+    value: u32,
+    divisor: u32,
+}
+
+impl Parts {
+    fn new(value: u32, granularity: Granularity) -> Self {
+        let mut divisor = 1;
+        if granularity == Granularity::Digits {
+            while divisor <= value / 10 {
+                divisor *= 10;
+            }
+        }
+        Self { value, divisor }
+    }
+}
+
+impl Iterator for Parts {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.divisor == 0 {
+            return None;
+        }
+        let part = self.value / self.divisor;
+        self.value %= self.divisor;
+        self.divisor /= 10;
+        Some(part)
+    }
+}
+
+fn concat(left: u128, right: u32) -> Option<u128> {
+    let mut shift = 10;
+    while shift <= right {
+        shift *= 10;
+    }
+    left.checked_mul(shift.into())?.checked_add(right.into())
+}
+
+fn concat_all(values: impl IntoIterator<Item = u32>) -> Result<u128, ErrorKind> {
+    values
+        .into_iter()
+        .try_fold(0, concat)
+        .ok_or(ErrorKind::Overflow)
+}
+
+impl Policy {
+    pub fn is_valid(&self) -> bool {
+        *self != Policy::FirstLastK(0)
+    }
+
+    pub fn apply(&self, tokens: &[Token], granularity: Granularity) -> Result<u128, ErrorKind> {
+        let mut values = tokens
+            .iter()
+            .flat_map(|token| Parts::new(token.value, granularity))
+            .peekable();
+        if !self.is_valid() {
+            return Err(ErrorKind::InvalidPolicy);
+        }
+        if values.peek().is_none() {
+            return Err(ErrorKind::NoDigit);
+        }
+        match *self {
+            Policy::FirstLast => {
+                let first = values.next().unwrap();
+                let last = values.last().unwrap_or(first);
+                concat_all([first, last])
+            }
+            Policy::Concatenate => concat_all(values),
+            Policy::MinMax => {
+                let (min, max) = values.fold((u32::MAX, 0), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                });
+                concat_all([min, max])
+            }
+            Policy::FirstLastK(k) => {
+                let count = values.clone().count();
+                let first = values.clone().take(k);
+                let last = values.skip(count.saturating_sub(k));
+                concat_all(first.chain(last))
+            }
+            Policy::Sum => Ok(values.map(u128::from).sum()),
+        }
+    }
+}
+
+#[test]
+fn test_policies() {
+    let mut tokens = Vec::new();
+    crate::DigitScanner::new(&crate::Vocabulary::english_compound())
+        .tokens(b"4twenty-one7x3", &mut tokens);
+    let apply = |policy: Policy, granularity| policy.apply(&tokens, granularity);
+    assert_eq!(apply(Policy::FirstLast, Granularity::Digits), Ok(43));
+    assert_eq!(apply(Policy::Concatenate, Granularity::Digits), Ok(42173));
+    assert_eq!(apply(Policy::Concatenate, Granularity::Numbers), Ok(42173));
+    assert_eq!(apply(Policy::MinMax, Granularity::Digits), Ok(17));
+    assert_eq!(apply(Policy::MinMax, Granularity::Numbers), Ok(321));
+    assert_eq!(apply(Policy::FirstLastK(2), Granularity::Digits), Ok(4273));
+    assert_eq!(
+        apply(Policy::FirstLastK(2), Granularity::Numbers),
+        Ok(42173)
+    );
+    assert_eq!(
+        apply(Policy::FirstLastK(0), Granularity::Digits),
+        Err(ErrorKind::InvalidPolicy)
+    );
+    assert_eq!(
+        apply(Policy::FirstLastK(3), Granularity::Digits),
+        Ok(421173)
+    );
+    assert_eq!(apply(Policy::Sum, Granularity::Digits), Ok(17));
+    assert_eq!(apply(Policy::Sum, Granularity::Numbers), Ok(35));
+    assert_eq!(apply(Policy::FirstLast, Granularity::Numbers), Ok(43));
+    assert_eq!(
+        Policy::Concatenate.apply(&tokens.repeat(10), Granularity::Digits),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        Policy::Sum.apply(&[], Granularity::Digits),
+        Err(ErrorKind::NoDigit)
+    );
+}
@@ -0,0 +1,269 @@
+use std::{cmp::Reverse, collections::VecDeque, ops::Range};
+
+use crate::{
+    unicode::{decimal_value, fold},
+    Vocabulary,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Digit,
+    Word,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+    pub kind: TokenKind,
+}
+
+impl Token {
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Output {
+    len: usize,
+    chars: usize,
+    value: u32,
+    kind: TokenKind,
+}
+
+// Aho-Corasick automaton over the ASCII digits plus a set of number words.
+// The goto function is fully expanded into a DFA, so scanning a line is one
+// table lookup per byte.
+#[derive(Clone, Debug)]
+pub struct DigitScanner {
+    transitions: Vec<[u32; 256]>,
+    outputs: Vec<Vec<Output>>,
+    unicode: bool,
+}
+
+impl DigitScanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut scanner = Self {
+            transitions: vec![[0; 256]],
+            outputs: vec![vec![]],
+            unicode: false,
+        };
+        for digit in 0..10 {
+            let pattern = char::from(b'0' + digit).to_string();
+            scanner.insert(&pattern, u32::from(digit), TokenKind::Digit);
+        }
+        for (word, value) in vocabulary.words() {
+            scanner.insert(word, value, TokenKind::Word);
+        }
+        scanner.link();
+        scanner
+    }
+
+    // Recognises every Unicode decimal digit and matches the words of the
+    // vocabulary case-insensitively.
+    pub fn unicode(vocabulary: &Vocabulary) -> Self {
+        let mut scanner = Self {
+            transitions: vec![[0; 256]],
+            outputs: vec![vec![]],
+            unicode: true,
+        };
+        for (word, value) in vocabulary.words() {
+            scanner.insert(
+                &word.chars().map(fold).collect::<String>(),
+                value,
+                TokenKind::Word,
+            );
+        }
+        scanner.link();
+        scanner
+    }
+
+    pub fn digits() -> Self {
+        Self::new(&Vocabulary::default())
+    }
+
+    pub fn english() -> Self {
+        Self::new(&Vocabulary::english())
+    }
+
+    fn insert(&mut self, pattern: &str, value: u32, kind: TokenKind) {
+        if pattern.is_empty() {
+            return;
+        }
+        let mut state = 0;
+        for &byte in pattern.as_bytes() {
+            let next = self.transitions[state][usize::from(byte)] as usize;
+            state = if next == 0 {
+                self.transitions.push([0; 256]);
+                self.outputs.push(vec![]);
+                let new_state = self.transitions.len() - 1;
+                self.transitions[state][usize::from(byte)] = new_state as u32;
+                new_state
+            } else {
+                next
+            };
+        }
+        self.outputs[state] = vec![Output {
+            len: pattern.len(),
+            chars: pattern.chars().count(),
+            value,
+            kind,
+        }];
+    }
+
+    fn link(&mut self) {
+        let mut fail = vec![0; self.transitions.len()];
+        let mut queue = self.transitions[0]
+            .iter()
+            .filter(|&&next| next != 0)
+            .map(|&next| next as usize)
+            .collect::<VecDeque<_>>();
+        while let Some(state) = queue.pop_front() {
+            let inherited = self.outputs[fail[state]].clone();
+            self.outputs[state].extend(inherited);
+            for byte in 0..256 {
+                let next = self.transitions[state][byte] as usize;
+                let fallback = self.transitions[fail[state]][byte];
+                if next == 0 {
+                    self.transitions[state][byte] = fallback;
+                } else {
+                    fail[next] = fallback as usize;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    // Fills `tokens` with every match that is not contained in a longer one,
+    // ordered by position. Overlapping words such as "eightwo" both survive.
+    pub fn tokens(&self, line: &[u8], tokens: &mut Vec<Token>) {
+        tokens.clear();
+        if self.unicode {
+            self.unicode_matches(line, tokens);
+        } else {
+            self.matches(line, tokens);
+        }
+        tokens.sort_unstable_by_key(|token| (token.start, Reverse(token.end)));
+        let mut reach = 0;
+        tokens.retain(|token| {
+            let keep = token.end > reach;
+            reach = reach.max(token.end);
+            keep
+        });
+    }
+
+    fn matches(&self, line: &[u8], tokens: &mut Vec<Token>) {
+        let mut state = 0;
+        for (i, &byte) in line.iter().enumerate() {
+            state = self.transitions[state][usize::from(byte)] as usize;
+            for output in &self.outputs[state] {
+                tokens.push(Token {
+                    start: i + 1 - output.len,
+                    end: i + 1,
+                    value: output.value,
+                    kind: output.kind,
+                });
+            }
+        }
+    }
+
+    // Feeds the case-folded characters through the automaton. Folding keeps
+    // the number of characters but not necessarily their byte lengths, so
+    // match starts are found by walking back over the original characters.
+    fn unicode_matches(&self, line: &[u8], tokens: &mut Vec<Token>) {
+        let mut state = 0;
+        let mut offset = 0;
+        for chunk in line.utf8_chunks() {
+            for (i, c) in chunk.valid().char_indices() {
+                let end = offset + i + c.len_utf8();
+                if let Some(value) = decimal_value(c) {
+                    tokens.push(Token {
+                        start: offset + i,
+                        end,
+                        value,
+                        kind: TokenKind::Digit,
+                    });
+                    state = 0;
+                    continue;
+                }
+                for &byte in fold(c).encode_utf8(&mut [0; 4]).as_bytes() {
+                    state = self.transitions[state][usize::from(byte)] as usize;
+                }
+                for output in &self.outputs[state] {
+                    let mut start = end;
+                    for _ in 0..output.chars {
+                        start -= 1;
+                        while line[start] & 0xC0 == 0x80 {
+                            start -= 1;
+                        }
+                    }
+                    tokens.push(Token {
+                        start,
+                        end,
+                        value: output.value,
+                        kind: output.kind,
+                    });
+                }
+            }
+            offset += chunk.valid().len() + chunk.invalid().len();
+            if !chunk.invalid().is_empty() {
+                state = 0;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_overlapping_words() {
+    let scanner = DigitScanner::english();
+    let mut tokens = Vec::new();
+    let mut values = |line: &[u8]| {
+        scanner.tokens(line, &mut tokens);
+        tokens.iter().map(|token| token.value).collect::<Vec<_>>()
+    };
+    assert_eq!(values(b"eightwo"), vec![8, 2]);
+    assert_eq!(values(b"zoneight234"), vec![1, 8, 2, 3, 4]);
+    assert_eq!(values(b"xtwone3four"), vec![2, 1, 3, 4]);
+    assert_eq!(values(b"abc"), vec![]);
+    let scanner = DigitScanner::new(&Vocabulary::from_iter([
+        ("twenty", 20),
+        ("twentyone", 21),
+        ("one", 1),
+    ]));
+    scanner.tokens(b"twentyone", &mut tokens);
+    assert_eq!(
+        tokens,
+        vec![Token {
+            start: 0,
+            end: 9,
+            value: 21,
+            kind: TokenKind::Word
+        }]
+    );
+}
+
+#[test]
+fn test_unicode() {
+    let scanner = DigitScanner::unicode(&Vocabulary::german());
+    let mut tokens = Vec::new();
+    scanner.tokens("x٣ZWEI\u{ff}FÜNF９".as_bytes(), &mut tokens);
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| (token.span(), token.value, token.kind))
+            .collect::<Vec<_>>(),
+        vec![
+            (1..3, 3, TokenKind::Digit),
+            (3..7, 2, TokenKind::Word),
+            (9..14, 5, TokenKind::Word),
+            (14..17, 9, TokenKind::Digit),
+        ]
+    );
+    DigitScanner::unicode(&Vocabulary::english()).tokens(b"EIGH\xffTWO", &mut tokens);
+    assert_eq!(
+        tokens.iter().map(|token| token.value).collect::<Vec<_>>(),
+        vec![2]
+    );
+}
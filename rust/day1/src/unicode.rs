@@ -0,0 +1,44 @@
+// First code point of every run of ten characters in the Unicode `Nd`
+// (decimal digit) category, as of Unicode 17.0. Each run holds the digits zero
+// through nine in order.
+const ZEROS: [u32; 77] = [
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66,
+    0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50, 0x11D50,
+    0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE,
+    0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+pub fn decimal_value(c: char) -> Option<u32> {
+    let c = u32::from(c);
+    let zero = match ZEROS.binary_search(&c) {
+        Ok(i) => ZEROS[i],
+        Err(0) => return None,
+        Err(i) => ZEROS[i - 1],
+    };
+    (c - zero < 10).then_some(c - zero)
+}
+
+// Lowercases characters whose lowercase form is a single character, so a
+// folded string has as many characters as the original.
+pub fn fold(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+#[test]
+fn test_decimal_value() {
+    assert_eq!(decimal_value('7'), Some(7));
+    assert_eq!(decimal_value('٣'), Some(3));
+    assert_eq!(decimal_value('９'), Some(9));
+    assert_eq!(decimal_value('𝟘'), Some(0));
+    assert_eq!(decimal_value('²'), None);
+    assert_eq!(decimal_value('a'), None);
+    assert_eq!(decimal_value('/'), None);
+    assert_eq!(fold('Ö'), 'ö');
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const TEENS: [(&str, u32); 10] = [
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+];
+
+const TENS: [(&str, u32); 8] = [
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+const GERMAN: [(&str, u32); 9] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const FRENCH: [(&str, u32); 9] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+const SPANISH: [(&str, u32); 9] = [
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+// Spelled numbers recognised next to the ASCII digits, mapped to their value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vocabulary(HashMap<String, u32>);
+
+impl Vocabulary {
+    pub fn english() -> Self {
+        ENGLISH.into_iter().collect()
+    }
+
+    // One through ninety-nine. Tens and units may be written together
+    // ("ninetynine") or hyphenated ("ninety-nine").
+    pub fn english_compound() -> Self {
+        let mut vocabulary = Self::english();
+        for (word, value) in TEENS {
+            vocabulary.insert(word, value);
+        }
+        for (tens, tens_value) in TENS {
+            vocabulary.insert(tens, tens_value);
+            for (unit, unit_value) in ENGLISH {
+                vocabulary.insert(format!("{tens}{unit}"), tens_value + unit_value);
+                vocabulary.insert(format!("{tens}-{unit}"), tens_value + unit_value);
+            }
+        }
+        vocabulary
+    }
+
+    pub fn german() -> Self {
+        GERMAN.into_iter().collect()
+    }
+
+    pub fn french() -> Self {
+        FRENCH.into_iter().collect()
+    }
+
+    pub fn spanish() -> Self {
+        SPANISH.into_iter().collect()
+    }
+
+    pub fn insert(&mut self, word: impl Into<String>, value: u32) {
+        self.0.insert(word.into(), value);
+    }
+
+    pub fn merge(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.0.iter().map(|(word, value)| (word.as_str(), *value))
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u32)> for Vocabulary {
+    fn from_iter<T: IntoIterator<Item = (S, u32)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(word, value)| (word.into(), value))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{parser, sum_powers, Config, Draw, Game, ParseError};
+
+// Running totals over games that arrive one at a time, or even one draw at a
+// time. Draws for an id that was seen before extend that game.
+#[derive(Clone, Debug)]
+pub struct Accumulator {
+    lowest: BTreeMap<u32, Config>,
+    colors: BTreeSet<String>,
+    maximum: Config,
+    // `None` while the sum does not fit into 64 bits.
+    power_sum: Option<u64>,
+    lines: usize,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            lowest: BTreeMap::new(),
+            colors: BTreeSet::new(),
+            maximum: Config::default(),
+            power_sum: Some(0),
+            lines: 0,
+        }
+    }
+}
+
+impl Accumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_game(&mut self, game: &Game) {
+        for draw in &game.draws {
+            self.add_draw(game.id, draw);
+        }
+    }
+
+    pub fn add_draw(&mut self, id: u32, draw: &Draw) {
+        let previous = self
+            .lowest
+            .get(&id)
+            .map_or(Some(0), |config| self.power(config));
+        self.lowest.entry(id).or_default().set_lowest_config(draw);
+        self.maximum.set_lowest_config(draw);
+        let mut new_colors = false;
+        for color in draw.colors() {
+            new_colors |= self.colors.insert(color.to_owned());
+        }
+        // A new color makes every game without it worth zero, so all powers
+        // are recomputed. So is an overflowed sum, as nothing can be taken
+        // out of it. Otherwise only this game changes.
+        let power = self.power(&self.lowest[&id]);
+        self.power_sum = match (new_colors, self.power_sum, previous) {
+            (false, Some(sum), Some(previous)) => {
+                power.and_then(|power| (sum - previous).checked_add(power))
+            }
+            _ => sum_powers(self.lowest.values().map(|config| self.power(config))),
+        };
+    }
+
+    // Parses and adds one line of a game log. Blank lines are skipped but
+    // counted, so errors carry the line number within the log.
+    pub fn add_line(&mut self, text: &str) -> Result<(), ParseError> {
+        self.lines += 1;
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        self.add_game(&parser::game(text, self.lines)?);
+        Ok(())
+    }
+
+    fn power(&self, config: &Config) -> Option<u64> {
+        config.power_set(self.colors.iter().map(String::as_str))
+    }
+
+    pub fn games(&self) -> usize {
+        self.lowest.len()
+    }
+
+    // Part 1 over the games so far.
+    pub fn possible_sum(&self, bag: &Config) -> u32 {
+        self.lowest
+            .iter()
+            .filter(|(_, lowest)| bag.check(lowest))
+            .map(|(id, _)| id)
+            .sum()
+    }
+
+    // Part 2 over the games so far, `None` if it does not fit into 64 bits.
+    pub fn power_sum(&self) -> Option<u64> {
+        self.power_sum
+    }
+
+    // The largest count of every color in any draw so far, i.e. the lowest
+    // bag that makes every game possible.
+    pub fn maximum(&self) -> &Config {
+        &self.maximum
+    }
+}
+
+#[test]
+fn test_accumulator() {
+    let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+    let bag = Config::new(12, 13, 14);
+    let mut accumulator = Accumulator::new();
+    let mut seen = String::new();
+    for line in input.lines() {
+        accumulator.add_line(line).unwrap();
+        seen.push_str(line);
+        seen.push('\n');
+        let games = crate::parse_games(&seen);
+        assert_eq!(
+            accumulator.possible_sum(&bag),
+            crate::example_1(&games, bag.clone())
+        );
+        assert_eq!(accumulator.power_sum(), crate::example_2(&games));
+    }
+    assert_eq!(accumulator.games(), 5);
+    assert_eq!(
+        (accumulator.possible_sum(&bag), accumulator.power_sum()),
+        (8, Some(2286))
+    );
+    assert_eq!(accumulator.maximum(), &Config::new(20, 13, 15));
+    assert_eq!(accumulator.add_line("Game 6 1 red").unwrap_err().line, 7);
+
+    let mut accumulator = Accumulator::new();
+    accumulator.add_draw(1, &Draw::from_iter([("red", 2)]));
+    accumulator.add_draw(2, &Draw::from_iter([("red", 3)]));
+    assert_eq!(accumulator.power_sum(), Some(5));
+    accumulator.add_draw(1, &Draw::from_iter([("red", 1), ("blue", 4)]));
+    assert_eq!(accumulator.power_sum(), Some(8));
+    accumulator.add_draw(2, &Draw::from_iter([("blue", 1)]));
+    assert_eq!(accumulator.power_sum(), Some(11));
+    assert_eq!(
+        accumulator.possible_sum(&Config::from_iter([("red", 2), ("blue", 4)])),
+        1
+    );
+
+    let mut accumulator = Accumulator::new();
+    accumulator
+        .add_line("Game 1: 100 red, 100 green, 100 blue, 100 teal, 100 purple")
+        .unwrap();
+    assert_eq!(accumulator.power_sum(), Some(10_000_000_000));
+    accumulator.add_draw(2, &Draw::from_iter([("red", 1 << 31), ("green", 1 << 31)]));
+    accumulator.add_draw(2, &Draw::from_iter([("blue", 1 << 31), ("teal", 1 << 31)]));
+    accumulator.add_draw(2, &Draw::from_iter([("purple", 1)]));
+    assert_eq!(accumulator.power_sum(), None);
+    // A new color leaves game 2 without it, so its power drops to zero.
+    accumulator.add_draw(1, &Draw::from_iter([("black", 1)]));
+    assert_eq!(accumulator.power_sum(), Some(10_000_000_000));
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+};
+
+use crate::{Config, Draw, Game};
+
+// A likelihood-ratio interval. `high` is `None` if the likelihood stays
+// within the interval all the way up to the search limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval {
+    pub low: u32,
+    pub high: Option<u32>,
+}
+
+// The largest bag the search considers per color. The likelihood needs a table
+// of log factorials up to the largest bag.
+pub const MAX_LIMIT: u32 = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EstimateError {
+    // Not strictly between 0 and 1, or NaN.
+    Confidence(f64),
+    // Above `MAX_LIMIT`, either as requested or as a count in the draws.
+    Limit(u32),
+}
+
+impl Display for EstimateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EstimateError::Confidence(confidence) => write!(
+                f,
+                "confidence must lie strictly between 0 and 1, got {confidence}"
+            ),
+            EstimateError::Limit(limit) => {
+                write!(f, "limit {limit} is above the maximum of {MAX_LIMIT}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EstimateError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub bag: Config,
+    pub log_likelihood: f64,
+    pub intervals: BTreeMap<String, Interval>,
+}
+
+// Every draw is a sample without replacement from the same bag, so a draw of
+// x_c cubes per color (k in total) from a bag of n_c cubes per color (N in
+// total) has the multivariate hypergeometric probability
+// prod_c C(n_c, x_c) / C(N, k). Cubes go back into the bag between draws.
+struct Model {
+    colors: Vec<String>,
+    counts: Vec<Vec<(u32, u32)>>,
+    totals: Vec<(u32, u32)>,
+    ln_factorial: Vec<f64>,
+}
+
+fn multiplicities(values: impl Iterator<Item = u32>) -> Vec<(u32, u32)> {
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+impl Model {
+    fn new(draws: &[&Draw], colors: Vec<String>, limit: u32) -> Self {
+        let counts = colors
+            .iter()
+            .map(|color| multiplicities(draws.iter().map(|draw| draw.get(color))))
+            .collect();
+        let totals = multiplicities(draws.iter().map(|draw| draw.0.values().sum()));
+        let max_total = limit as usize * colors.len();
+        let mut ln_factorial = vec![0.0; max_total + 1];
+        for i in 1..=max_total {
+            ln_factorial[i] = ln_factorial[i - 1] + (i as f64).ln();
+        }
+        Self {
+            colors,
+            counts,
+            totals,
+            ln_factorial,
+        }
+    }
+
+    fn ln_choose(&self, n: u32, k: u32) -> f64 {
+        let (n, k) = (n as usize, k as usize);
+        self.ln_factorial[n] - self.ln_factorial[k] - self.ln_factorial[n - k]
+    }
+
+    fn log_likelihood(&self, bag: &[u32]) -> f64 {
+        let total = bag.iter().sum();
+        let colors = self
+            .counts
+            .iter()
+            .zip(bag)
+            .flat_map(|(counts, &n)| {
+                counts
+                    .iter()
+                    .map(move |&(x, times)| f64::from(times) * self.ln_choose(n, x))
+            })
+            .sum::<f64>();
+        let totals = self
+            .totals
+            .iter()
+            .map(|&(k, times)| f64::from(times) * self.ln_choose(total, k))
+            .sum::<f64>();
+        colors - totals
+    }
+}
+
+// Upper quantile of the standard normal distribution (Abramowitz and Stegun
+// 26.2.23, absolute error below 4.5e-4).
+fn normal_quantile(p: f64) -> f64 {
+    let t = (-2.0 * p.ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+// Maximum-likelihood estimate of the bag behind `draws`, searched by
+// local search from the lowest feasible bag with at most `limit` cubes
+// per color. The interval of each color holds every count whose likelihood,
+// with the other colors at their estimate, is within the chi-squared cutoff
+// for the given `confidence` (e.g. 0.95), which must lie strictly between 0
+// and 1.
+pub fn estimate_bag<'d>(
+    draws: impl IntoIterator<Item = &'d Draw>,
+    limit: u32,
+    confidence: f64,
+) -> Result<Estimate, EstimateError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(EstimateError::Confidence(confidence));
+    }
+    let draws = draws.into_iter().collect::<Vec<_>>();
+    let colors = draws
+        .iter()
+        .flat_map(|draw| draw.colors())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let lowest = colors
+        .iter()
+        .map(|color| draws.iter().map(|draw| draw.get(color)).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let limit = lowest.iter().copied().max().unwrap_or(0).max(limit);
+    if limit > MAX_LIMIT {
+        return Err(EstimateError::Limit(limit));
+    }
+    let model = Model::new(&draws, colors, limit);
+
+    // Single colors move by one cube, but so does the whole bag at once:
+    // growing every color together keeps the proportions, which a move along
+    // one color alone can not.
+    let mut bag = lowest.clone();
+    let mut best = model.log_likelihood(&bag);
+    let moves = (0..bag.len())
+        .map(|i| vec![i])
+        .chain([(0..bag.len()).collect()])
+        .collect::<Vec<_>>();
+    loop {
+        let mut improved = false;
+        for colors in &moves {
+            for step in [1, -1] {
+                let candidate = bag
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &count)| {
+                        if !colors.contains(&i) {
+                            return Some(count);
+                        }
+                        let count = count.checked_add_signed(step)?;
+                        (lowest[i]..=limit).contains(&count).then_some(count)
+                    })
+                    .collect::<Option<Vec<_>>>();
+                let Some(candidate) = candidate else {
+                    continue;
+                };
+                let likelihood = model.log_likelihood(&candidate);
+                if likelihood > best + 1e-9 {
+                    (bag, best) = (candidate, likelihood);
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    let cutoff = normal_quantile((1.0 - confidence) / 2.0).powi(2) / 2.0;
+    let intervals = (0..lowest.len())
+        .map(|i| {
+            let estimate = bag[i];
+            let mut scratch = bag.clone();
+            let mut within = |count: u32| {
+                scratch[i] = count;
+                best - model.log_likelihood(&scratch) <= cutoff
+            };
+            let low = (lowest[i]..estimate)
+                .rev()
+                .take_while(|&count| within(count))
+                .last()
+                .unwrap_or(estimate);
+            let high = (estimate + 1..=limit)
+                .take_while(|&count| within(count))
+                .last()
+                .unwrap_or(estimate);
+            let high = (high < limit).then_some(high);
+            (model.colors[i].clone(), Interval { low, high })
+        })
+        .collect();
+
+    Ok(Estimate {
+        bag: model
+            .colors
+            .iter()
+            .cloned()
+            .zip(bag.iter().copied())
+            .collect(),
+        log_likelihood: best,
+        intervals,
+    })
+}
+
+pub fn estimate_from_games(
+    games: &[Game],
+    limit: u32,
+    confidence: f64,
+) -> Result<Estimate, EstimateError> {
+    estimate_bag(games.iter().flat_map(|game| &game.draws), limit, confidence)
+}
+
+#[test]
+fn test_estimate_bag() {
+    let games = crate::parse_games("Game 1: 1 red, 1 blue");
+    let estimate = estimate_from_games(&games, 100, 0.95).unwrap();
+    assert_eq!(estimate.bag, Config::from_iter([("red", 1), ("blue", 1)]));
+    assert!(estimate.log_likelihood.abs() < 1e-9);
+    // P(one of each) = 2 / (red + 1) with one blue cube.
+    assert_eq!(
+        estimate.intervals["red"],
+        Interval {
+            low: 1,
+            high: Some(12)
+        }
+    );
+
+    let games = crate::parse_games("Game 1: 1 red, 1 blue; 2 red; 2 blue");
+    let estimate = estimate_from_games(&games, 50, 0.95).unwrap();
+    assert_eq!(estimate.bag, Config::from_iter([("red", 50), ("blue", 50)]));
+    assert_eq!(estimate.intervals["blue"].high, None);
+
+    let games = crate::parse_games(
+        "Game 1: 2 red, 1 blue; 3 red; 2 red, 1 blue; 3 red; 1 red, 2 blue; 3 red; 3 red",
+    );
+    let estimate = estimate_from_games(&games, 100, 0.95).unwrap();
+    let (red, blue) = (estimate.bag.get("red"), estimate.bag.get("blue"));
+    assert!(red > 3 * blue, "{red} red, {blue} blue");
+    let interval = estimate.intervals["red"];
+    assert!(interval.low <= red && interval.high.is_none_or(|high| red <= high));
+
+    for confidence in [0.0, 1.0, -0.5, f64::NAN] {
+        assert!(matches!(
+            estimate_from_games(&games, 100, confidence),
+            Err(EstimateError::Confidence(_))
+        ));
+    }
+    assert_eq!(
+        estimate_from_games(&games, u32::MAX, 0.95),
+        Err(EstimateError::Limit(u32::MAX))
+    );
+    let games = crate::parse_games("Game 1: 70000 red");
+    assert_eq!(
+        estimate_from_games(&games, 100, 0.95),
+        Err(EstimateError::Limit(70000))
+    );
+}
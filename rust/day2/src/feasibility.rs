@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use crate::{Config, Game};
+
+// The first draw of a game that does not fit into the bag, with the number of
+// cubes per color that the bag is short of.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    pub draw: usize,
+    pub excess: Config,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Feasibility {
+    pub possible: Vec<u32>,
+    pub impossible: Vec<(u32, Failure)>,
+}
+
+impl Game {
+    pub fn failure(&self, bag: &Config) -> Option<Failure> {
+        self.draws.iter().enumerate().find_map(|(i, draw)| {
+            let excess = draw
+                .0
+                .iter()
+                .filter(|(color, amount)| **amount > bag.get(color))
+                .map(|(color, amount)| (color.as_str(), amount - bag.get(color)))
+                .collect::<Config>();
+            (!excess.0.is_empty()).then_some(Failure { draw: i, excess })
+        })
+    }
+}
+
+impl Feasibility {
+    pub fn id_sum(&self) -> u32 {
+        self.possible.iter().sum()
+    }
+}
+
+pub fn feasibility(games: &[Game], bag: &Config) -> Feasibility {
+    let mut result = Feasibility::default();
+    for game in games {
+        match game.failure(bag) {
+            Some(failure) => result.impossible.push((game.id, failure)),
+            None => result.possible.push(game.id),
+        }
+    }
+    result
+}
+
+impl Display for Feasibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "possible games: {:?}", self.possible)?;
+        for (id, failure) in &self.impossible {
+            let mut colors = failure.excess.colors().collect::<Vec<_>>();
+            colors.sort_unstable();
+            let excess = colors
+                .iter()
+                .map(|color| format!("{} {color}", failure.excess.get(color)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "game {id} is impossible: draw {} needs {excess} more",
+                failure.draw + 1
+            )?;
+        }
+        writeln!(f, "sum of possible game ids: {}", self.id_sum())
+    }
+}
+
+#[test]
+fn test_feasibility() {
+    let games = crate::parse_games(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+    );
+    let result = feasibility(&games, &"12 red, 13 green, 14 blue".parse().unwrap());
+    assert_eq!(result.possible, vec![1]);
+    assert_eq!(
+        result.impossible,
+        vec![
+            (
+                3,
+                Failure {
+                    draw: 0,
+                    excess: Config::from_iter([("red", 8)])
+                }
+            ),
+            (
+                4,
+                Failure {
+                    draw: 2,
+                    excess: Config::from_iter([("blue", 1), ("red", 2)])
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        result.to_string(),
+        "possible games: [1]
+game 3 is impossible: draw 1 needs 8 red more
+game 4 is impossible: draw 3 needs 1 blue, 2 red more
+sum of possible game ids: 1
+"
+    );
+}
@@ -1,182 +1,182 @@
-use std::{collections::HashMap, str::Chars};
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
 
 use aoc_traits::AdventOfCodeDay;
 
-#[derive(Debug)]
-struct Config(HashMap<Colors, u32>);
-#[derive(PartialEq, Eq, Hash, Debug)]
-enum Colors {
-    Red,
-    Blue,
-    Green,
+mod accumulator;
+mod analysis;
+mod feasibility;
+mod optimizer;
+mod parser;
+mod statistics;
+
+pub use accumulator::Accumulator;
+pub use analysis::{
+    estimate_bag, estimate_from_games, Estimate, EstimateError, Interval, MAX_LIMIT,
+};
+pub use feasibility::{feasibility, Failure, Feasibility};
+pub use optimizer::{cheapest_bag, smallest_bag, Optimum};
+pub use parser::{Expected, ParseError};
+pub use statistics::{statistics, ColorStats, GameStats, Statistics, Summary};
+
+// Cube counts per color. Colors are whatever names appear in the input; a
+// color that is not present counts as zero.
+#[derive(Clone, Debug, Default)]
+pub struct Config(HashMap<String, u32>);
+
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .keys()
+            .chain(other.0.keys())
+            .all(|color| self.get(color) == other.get(color))
+    }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        let mut map = HashMap::new();
-        map.insert(Colors::Blue, 0);
-        map.insert(Colors::Red, 0);
-        map.insert(Colors::Green, 0);
-        Self(map)
+impl Eq for Config {}
+
+impl<S: Into<String>> FromIterator<(S, u32)> for Config {
+    fn from_iter<T: IntoIterator<Item = (S, u32)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(color, amount)| (color.into(), amount))
+                .collect(),
+        )
     }
 }
+
 impl Config {
-    fn new(red: u32, green: u32, blue: u32) -> Self {
-        let mut map = HashMap::new();
-        map.insert(Colors::Blue, blue);
-        map.insert(Colors::Red, red);
-        map.insert(Colors::Green, green);
-        Self(map)
+    pub fn new(red: u32, green: u32, blue: u32) -> Self {
+        Self::from_iter([("red", red), ("green", green), ("blue", blue)])
     }
 
-    fn set_lowest_config(&mut self, draw: Self) {
-        if let Some(x) = self.0.get_mut(&Colors::Blue) {
-            *x = std::cmp::max(*x, *draw.0.get(&Colors::Blue).unwrap());
-        }
-        if let Some(x) = self.0.get_mut(&Colors::Red) {
-            *x = std::cmp::max(*x, *draw.0.get(&Colors::Red).unwrap());
-        }
-        if let Some(x) = self.0.get_mut(&Colors::Green) {
-            *x = std::cmp::max(*x, *draw.0.get(&Colors::Green).unwrap());
+    pub fn get(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or_default()
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    fn set_lowest_config(&mut self, draw: &Self) {
+        for (color, amount) in &draw.0 {
+            let x = self.0.entry(color.clone()).or_default();
+            *x = std::cmp::max(*x, *amount);
         }
     }
 
-    fn power_set(self) -> u32 {
-        self.0.values().cloned().reduce(|a, b| a * b).unwrap()
+    // `None` if the power does not fit into 64 bits, which takes many colors
+    // or large counts.
+    pub fn power_set<'c>(&self, colors: impl IntoIterator<Item = &'c str>) -> Option<u64> {
+        colors.into_iter().try_fold(1u64, |power, color| {
+            power.checked_mul(self.get(color).into())
+        })
     }
 
-    fn set_color(&mut self, color: Colors, amount: u32) {
-        self.0.insert(color, amount);
+    fn set_color(&mut self, color: &str, amount: u32) {
+        self.0.insert(color.to_owned(), amount);
     }
 
-    fn check(&self, other: Self) -> bool {
-        let mut result = self.0.get(&Colors::Blue).unwrap() >= other.0.get(&Colors::Blue).unwrap();
-        result &= self.0.get(&Colors::Red).unwrap() >= other.0.get(&Colors::Red).unwrap();
-        result &= self.0.get(&Colors::Green).unwrap() >= other.0.get(&Colors::Green).unwrap();
-        result
+    pub fn check(&self, other: &Self) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(color, amount)| self.get(color) >= *amount)
     }
 }
 
-fn parse_game_id(char_iter: &mut Chars) -> u32 {
-    let mut game_id = String::new();
-    for x in char_iter.by_ref() {
-        if x.is_ascii_digit() {
-            game_id.push(x);
-        } else if x != ':' {
-            panic!()
-        } else {
-            break;
-        }
+// Parses a bag written like a draw, e.g. "12 red, 13 green, 14 blue".
+impl FromStr for Config {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::draw(s)
     }
-    char_iter.next(); //remove ' '
-    game_id.parse().unwrap()
 }
 
-fn parse_color(char_iter: &mut Chars) -> Colors {
-    match char_iter.next() {
-        Some('r') => {
-            char_iter.next(); //'e';
-            char_iter.next(); //'d';
-            Colors::Red
-        }
-        Some('g') => {
-            char_iter.next(); //'r';
-            char_iter.next(); //'e';
-            char_iter.next(); //'e';
-            char_iter.next(); //'n';
-            Colors::Green
-        }
-        Some('b') => {
-            char_iter.next(); //'l';
-            char_iter.next(); //'u';
-            char_iter.next(); //'e';
-            Colors::Blue
-        }
-        x => unreachable!("found {x:?}"),
-    }
+pub type Draw = Config;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
 }
 
-fn parse_next_set(char_iter: &mut Chars) -> (bool, Config) {
-    let mut config = Config::default();
-    let mut current_draw = String::new();
-    loop {
-        match char_iter.next() {
-            Some(';') => {
-                char_iter.next(); // ' '
-                break (true, config);
-            }
-            Some(',') => {
-                //next draw remove ' ',
-                char_iter.next().unwrap();
-            }
-            Some(' ') => {
-                config.set_color(parse_color(char_iter), current_draw.parse().unwrap());
-                current_draw.clear();
-            }
-            Some(x) => current_draw.push(x),
-
-            None => break (false, config),
+impl Game {
+    pub fn lowest_config(&self) -> Config {
+        let mut config = Config::default();
+        for draw in &self.draws {
+            config.set_lowest_config(draw);
         }
+        config
+    }
+
+    pub fn is_possible(&self, config: &Config) -> bool {
+        self.draws.iter().all(|draw| config.check(draw))
     }
 }
 
-fn example_2(s: &str) -> u32 {
-    s.lines()
-        .map(|s| &s[s.find(':').unwrap() + 1..])
-        .map(|s| {
-            let mut chars = s.trim().chars();
-            let mut current_lowest_config = Config::default();
-            loop {
-                let (cont, next_set) = parse_next_set(&mut chars);
-                current_lowest_config.set_lowest_config(next_set);
-                if !cont {
-                    break;
-                }
-            }
-            current_lowest_config.power_set()
-        })
-        .sum()
+pub fn try_parse_games(s: &str) -> Result<Vec<Game>, ParseError> {
+    parser::games(s)
 }
 
-fn example_1(s: &str, config: Config) -> u32 {
-    s.lines()
-        .map(|s| &s.trim()["Game ".len()..])
-        .map(|s| {
-            let mut chars = s.chars();
-            let game_id = parse_game_id(&mut chars);
-            loop {
-                let (cont, next_set) = parse_next_set(&mut chars);
-                if !config.check(next_set) {
-                    return 0;
-                }
-                if !cont {
-                    break;
-                }
-            }
-            game_id
-        })
+// Panics with the rendered parse error on malformed input.
+pub fn parse_games(s: &str) -> Vec<Game> {
+    try_parse_games(s).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn colors(games: &[Game]) -> BTreeSet<&str> {
+    games
+        .iter()
+        .flat_map(|game| game.draws.iter().flat_map(Config::colors))
+        .collect()
+}
+
+// `None` if a power or their sum does not fit into 64 bits.
+pub(crate) fn sum_powers(powers: impl IntoIterator<Item = Option<u64>>) -> Option<u64> {
+    powers
+        .into_iter()
+        .try_fold(0u64, |sum, power| sum.checked_add(power?))
+}
+
+fn example_2(games: &[Game]) -> Option<u64> {
+    let colors = colors(games);
+    sum_powers(
+        games
+            .iter()
+            .map(|game| game.lowest_config().power_set(colors.iter().copied())),
+    )
+}
+
+fn example_1(games: &[Game], config: Config) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(&config))
+        .map(|game| game.id)
         .sum()
 }
 
 pub struct Day2Solver;
 
 impl<'a> AdventOfCodeDay<'a> for Day2Solver {
-    type ParsedInput = &'a str;
+    type ParsedInput = Vec<Game>;
 
     type Part1Output = u32;
 
-    type Part2Output = u32;
+    type Part2Output = u64;
 
     fn solve_part1(input: &Self::ParsedInput) -> Self::Part1Output {
         example_1(input, Config::new(12, 13, 14))
     }
 
     fn solve_part2(input: &Self::ParsedInput) -> Self::Part2Output {
-        example_2(input)
+        example_2(input).unwrap_or_else(|| panic!("the power sum does not fit into 64 bits"))
     }
 
     fn parse_input(input: &'a str) -> Self::ParsedInput {
-        input
+        parse_games(input)
     }
 }
 
@@ -187,8 +187,8 @@ fn test_example_1() {
     Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
     Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
     Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-    //example_1(input, Config::new(12, 12, 12));
-    assert_eq!(example_1(input, Config::new(12, 13, 14)), 8);
+    //example_1(&parse_games(input), Config::new(12, 12, 12));
+    assert_eq!(example_1(&parse_games(input), Config::new(12, 13, 14)), 8);
 }
 
 #[test]
@@ -198,8 +198,64 @@ fn test_example_2() {
     Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
     Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
     Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-    //example_1(input, Config::new(12, 12, 12));
-    assert_eq!(example_2(input), 2286);
+    //example_1(&parse_games(input), Config::new(12, 12, 12));
+    assert_eq!(example_2(&parse_games(input)), Some(2286));
+}
+
+#[test]
+fn test_parse_games() {
+    let games = parse_games(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 12: 1 blue",
+    );
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].id, 1);
+    assert_eq!(games[0].draws.len(), 3);
+    assert_eq!(games[0].draws[1], Config::new(1, 2, 6));
+    assert_eq!(games[0].lowest_config(), Config::new(4, 2, 6));
+    assert_eq!(games[1].id, 12);
+    assert_eq!(games[1].draws, vec![Config::new(0, 0, 1)]);
+}
+
+#[test]
+fn test_more_colors() {
+    let games = parse_games(
+        "Game 1: 3 purple, 4 red; 1 red, 2 green, 6 blue; 2 teal
+    Game 2: 1 blue, 2 purple, 1 teal, 1 red, 5 green",
+    );
+    assert_eq!(
+        colors(&games).into_iter().collect::<Vec<_>>(),
+        vec!["blue", "green", "purple", "red", "teal"]
+    );
+    assert_eq!(
+        games[0].lowest_config(),
+        Config::from_iter([
+            ("purple", 3),
+            ("red", 4),
+            ("green", 2),
+            ("blue", 6),
+            ("teal", 2)
+        ])
+    );
+    assert_eq!(example_2(&games), Some(288 + 10));
+    let bag = Config::from_iter([("purple", 3), ("red", 4), ("green", 5), ("blue", 6)]);
+    assert_eq!(example_1(&games, bag), 0);
+    let bag = Config::from_iter([
+        ("purple", 3),
+        ("red", 4),
+        ("green", 5),
+        ("blue", 6),
+        ("teal", 1),
+    ]);
+    assert_eq!(example_1(&games, bag), 2);
+
+    let games = parse_games(
+        "Game 1: 100 red, 100 green, 100 blue, 100 teal, 100 purple
+    Game 2: 99 red, 100 green, 100 blue, 100 teal; 100 purple",
+    );
+    assert_eq!(example_2(&games), Some(10_000_000_000 + 9_900_000_000));
+    let games = parse_games("Game 1: 10000 red, 10000 green, 10000 blue, 10000 teal, 10000 purple");
+    assert_eq!(example_2(&games), None);
 }
 
 #[test]
@@ -304,6 +360,9 @@ fn my_example_1() {
     Game 98: 3 green, 4 blue, 7 red; 7 red, 8 green; 7 green, 16 red, 1 blue; 8 green, 2 blue, 4 red; 5 green, 3 blue, 18 red
     Game 99: 6 green, 12 red, 1 blue; 5 blue, 1 red, 7 green; 5 green, 7 red, 10 blue; 8 blue, 1 red, 7 green; 17 red, 4 blue, 9 green
     Game 100: 6 blue, 10 green; 3 green, 4 blue, 1 red; 7 blue, 1 red, 12 green";
-    assert_eq!(2348, example_1(input, Config::new(12, 13, 14)));
-    assert_eq!(76008, example_2(input))
+    assert_eq!(
+        2348,
+        example_1(&parse_games(input), Config::new(12, 13, 14))
+    );
+    assert_eq!(Some(76008), example_2(&parse_games(input)))
 }
@@ -0,0 +1,151 @@
+use crate::{colors, Config, Game};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Optimum {
+    pub bag: Config,
+    pub cost: u64,
+    // Ids of every game the bag makes possible, not only the requested ones.
+    pub admitted: Vec<u32>,
+}
+
+impl Optimum {
+    fn new(games: &[Game], bag: Config, cost: u64) -> Self {
+        let admitted = games
+            .iter()
+            .filter(|game| game.is_possible(&bag))
+            .map(|game| game.id)
+            .collect();
+        Self {
+            bag,
+            cost,
+            admitted,
+        }
+    }
+}
+
+// Branch and bound over one color at a time. A bag that admits a set of games
+// only needs the maximum of their lowest configs, so every color is set to
+// the lowest count of some game (or zero).
+struct Search<'g> {
+    colors: Vec<&'g str>,
+    costs: Vec<u64>,
+    lowest: Vec<Vec<u32>>,
+    k: usize,
+    best: Option<(u64, Vec<u32>)>,
+}
+
+impl Search<'_> {
+    // The bag needs at least the k-th smallest count of every color that is
+    // still open, among the games that fit the colors chosen so far.
+    fn bound(&self, color: usize, compatible: &[usize]) -> u64 {
+        (color..self.colors.len())
+            .map(|c| {
+                let mut counts = compatible
+                    .iter()
+                    .map(|&game| self.lowest[game][c])
+                    .collect::<Vec<_>>();
+                counts.sort_unstable();
+                let kth = self.k.checked_sub(1).map_or(0, |i| counts[i]);
+                self.costs[c] * u64::from(kth)
+            })
+            .sum()
+    }
+
+    fn search(&mut self, color: usize, compatible: Vec<usize>, chosen: &mut Vec<u32>, cost: u64) {
+        if compatible.len() < self.k {
+            return;
+        }
+        let bound = cost + self.bound(color, &compatible);
+        if self.best.as_ref().is_some_and(|(best, _)| bound >= *best) {
+            return;
+        }
+        if color == self.colors.len() {
+            self.best = Some((cost, chosen.clone()));
+            return;
+        }
+        let mut thresholds = compatible
+            .iter()
+            .map(|&game| self.lowest[game][color])
+            .chain([0])
+            .collect::<Vec<_>>();
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        for threshold in thresholds {
+            let next = compatible
+                .iter()
+                .copied()
+                .filter(|&game| self.lowest[game][color] <= threshold)
+                .collect::<Vec<_>>();
+            chosen.push(threshold);
+            let added = self.costs[color] * u64::from(threshold);
+            self.search(color + 1, next, chosen, cost + added);
+            chosen.pop();
+        }
+    }
+}
+
+// Smallest bag, by total number of cubes, that makes at least `k` games
+// possible. `None` if there are fewer than `k` games.
+pub fn smallest_bag(games: &[Game], k: usize) -> Option<Optimum> {
+    let colors = colors(games).into_iter().collect::<Vec<_>>();
+    let lowest = games
+        .iter()
+        .map(|game| {
+            let config = game.lowest_config();
+            colors.iter().map(|color| config.get(color)).collect()
+        })
+        .collect();
+    let mut search = Search {
+        costs: vec![1; colors.len()],
+        colors,
+        lowest,
+        k,
+        best: None,
+    };
+    search.search(0, (0..games.len()).collect(), &mut Vec::new(), 0);
+    let (cost, counts) = search.best?;
+    let bag = search.colors.iter().copied().zip(counts).collect();
+    Some(Optimum::new(games, bag, cost))
+}
+
+// Cheapest bag that makes every game in `ids` possible, where a cube costs
+// its entry in `costs` (colors without one are free). `None` if an id is
+// unknown.
+pub fn cheapest_bag(games: &[Game], ids: &[u32], costs: &Config) -> Option<Optimum> {
+    let mut bag = Config::default();
+    for id in ids {
+        let game = games.iter().find(|game| game.id == *id)?;
+        bag.set_lowest_config(&game.lowest_config());
+    }
+    let cost = bag
+        .colors()
+        .map(|color| u64::from(costs.get(color)) * u64::from(bag.get(color)))
+        .sum();
+    Some(Optimum::new(games, bag, cost))
+}
+
+#[test]
+fn test_optimizer() {
+    let games = crate::parse_games(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+    Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+    Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    );
+    let optimum = smallest_bag(&games, 1).unwrap();
+    assert_eq!((optimum.bag, optimum.cost), (Config::new(1, 3, 4), 8));
+    assert_eq!(optimum.admitted, vec![2]);
+    let optimum = smallest_bag(&games, 3).unwrap();
+    assert_eq!((optimum.bag, optimum.cost), (Config::new(6, 3, 6), 15));
+    assert_eq!(optimum.admitted, vec![1, 2, 5]);
+    assert_eq!(smallest_bag(&games, 5).unwrap().cost, 48);
+    assert_eq!(smallest_bag(&games, 0).unwrap().cost, 0);
+    assert_eq!(smallest_bag(&games, 6), None);
+
+    let costs = Config::new(1, 2, 3);
+    let optimum = cheapest_bag(&games, &[1, 3], &costs).unwrap();
+    assert_eq!((optimum.bag, optimum.cost), (Config::new(20, 13, 6), 64));
+    assert_eq!(optimum.admitted, vec![1, 2, 3, 5]);
+    assert_eq!(cheapest_bag(&games, &[7], &costs), None);
+}
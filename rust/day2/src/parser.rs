@@ -0,0 +1,243 @@
+use std::fmt::Display;
+
+use crate::{Draw, Game};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expected {
+    Game,
+    Number,
+    SmallNumber,
+    Colon,
+    Color,
+    // Between draws or sets of a game.
+    Separator,
+    // Between the draws of a bag.
+    Comma,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Game => write!(f, "\"Game\""),
+            Expected::Number => write!(f, "a number"),
+            Expected::SmallNumber => write!(f, "a number below {}", u64::from(u32::MAX) + 1),
+            Expected::Colon => write!(f, "':'"),
+            Expected::Color => write!(f, "a color"),
+            Expected::Separator => write!(f, "',', ';' or the end of the line"),
+            Expected::Comma => write!(f, "',' or the end of the input"),
+        }
+    }
+}
+
+// `line` and `column` are 1-based, the column counts characters. `text` is
+// the offending line, so the error can point at the bad input on its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: Expected,
+    pub found: Option<char>,
+    pub text: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found {
+            Some(found) => writeln!(f, "{found:?}")?,
+            None => writeln!(f, "the end of the line")?,
+        }
+        // Tabs are kept so the caret lines up with the text above it.
+        let indent = self
+            .text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        writeln!(f, "{}", self.text)?;
+        write!(f, "{indent}^")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Cursor<'a> {
+    text: &'a str,
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            text,
+            position: 0,
+            line,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn error(&self, expected: Expected) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.text[..self.position].chars().count() + 1,
+            expected,
+            found: self.peek(),
+            text: self.text.to_owned(),
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.text[self.position..];
+        let end = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    fn skip_spaces(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_spaces();
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char, expected: Expected) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, ParseError> {
+        self.skip_spaces();
+        let start = self.position;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error(Expected::Number));
+        }
+        digits.parse().map_err(|_| {
+            self.position = start;
+            self.error(Expected::SmallNumber)
+        })
+    }
+
+    fn color(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_spaces();
+        let color = self.take_while(char::is_alphabetic);
+        if color.is_empty() {
+            return Err(self.error(Expected::Color));
+        }
+        Ok(color)
+    }
+
+    fn draw(&mut self) -> Result<Draw, ParseError> {
+        let mut draw = Draw::default();
+        loop {
+            let amount = self.number()?;
+            draw.set_color(self.color()?, amount);
+            if !self.eat(',') {
+                break Ok(draw);
+            }
+        }
+    }
+
+    fn end(&mut self, expected: Expected) -> Result<(), ParseError> {
+        self.skip_spaces();
+        match self.peek() {
+            Some(_) => Err(self.error(expected)),
+            None => Ok(()),
+        }
+    }
+
+    // Game <id>: <draw>; <draw>; ...
+    fn game(&mut self) -> Result<Game, ParseError> {
+        self.skip_spaces();
+        if !self.text[self.position..].starts_with("Game") {
+            return Err(self.error(Expected::Game));
+        }
+        self.position += "Game".len();
+        let id = self.number()?;
+        self.expect(':', Expected::Colon)?;
+        let mut draws = vec![self.draw()?];
+        while self.eat(';') {
+            draws.push(self.draw()?);
+        }
+        self.end(Expected::Separator)?;
+        Ok(Game { id, draws })
+    }
+}
+
+// Blank lines are skipped, but still count for the line numbers.
+pub(crate) fn games(s: &str) -> Result<Vec<Game>, ParseError> {
+    s.lines()
+        .zip(1..)
+        .filter(|(text, _)| !text.trim().is_empty())
+        .map(|(text, line)| game(text, line))
+        .collect()
+}
+
+pub(crate) fn game(text: &str, line: usize) -> Result<Game, ParseError> {
+    Cursor::new(text, line).game()
+}
+
+pub(crate) fn draw(s: &str) -> Result<Draw, ParseError> {
+    let mut cursor = Cursor::new(s, 1);
+    let draw = cursor.draw()?;
+    cursor.end(Expected::Comma)?;
+    Ok(draw)
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = games("Game 1: 1 red\n\nGame 3: 4 purple, 2 blue 1 green").unwrap_err();
+    assert_eq!((error.line, error.column), (3, 26));
+    assert_eq!(error.expected, Expected::Separator);
+    assert_eq!(error.found, Some('1'));
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 26: expected ',', ';' or the end of the line, found '1'\n\
+         Game 3: 4 purple, 2 blue 1 green\n\
+         \x20                        ^"
+    );
+
+    let error = |s: &str| {
+        let error = games(s).unwrap_err();
+        (error.column, error.expected, error.found)
+    };
+    assert_eq!(error("Gam 1: 1 red"), (1, Expected::Game, Some('G')));
+    assert_eq!(error("Game x: 1 red"), (6, Expected::Number, Some('x')));
+    assert_eq!(error("Game 1 1 red"), (8, Expected::Colon, Some('1')));
+    assert_eq!(error("Game 1: 1 red;"), (15, Expected::Number, None));
+    assert_eq!(error("Game 1: 1 2 red"), (11, Expected::Color, Some('2')));
+    assert_eq!(
+        error("Game 1: 4294967296 red"),
+        (9, Expected::SmallNumber, Some('4'))
+    );
+
+    assert_eq!(
+        games("Game 3: 4 purple")
+            .unwrap()
+            .into_iter()
+            .map(|game| game.lowest_config())
+            .collect::<Vec<_>>(),
+        vec![Draw::from_iter([("purple", 4)])]
+    );
+    assert_eq!(
+        draw("12 red, 13 green; 14 blue").unwrap_err().expected,
+        Expected::Comma
+    );
+}
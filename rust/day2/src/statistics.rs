@@ -0,0 +1,339 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::{self, Write},
+};
+
+use crate::{colors, sum_powers, Draw, Game};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStats {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+impl ColorStats {
+    // A draw without the color counts as zero cubes of it.
+    fn new(draws: &[&Draw], color: &str) -> Self {
+        let counts = draws.iter().map(|draw| draw.get(color));
+        let sum = counts.clone().map(u64::from).sum::<u64>();
+        Self {
+            min: counts.clone().min().unwrap_or(0),
+            max: counts.max().unwrap_or(0),
+            mean: if draws.is_empty() {
+                0.0
+            } else {
+                sum as f64 / draws.len() as f64
+            },
+        }
+    }
+
+    fn per_color(draws: &[&Draw], colors: &[String]) -> BTreeMap<String, Self> {
+        colors
+            .iter()
+            .map(|color| (color.clone(), Self::new(draws, color)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameStats {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+    pub colors: BTreeMap<String, ColorStats>,
+    // `None` if the power does not fit into 64 bits.
+    pub power: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub draws: usize,
+    pub colors: BTreeMap<String, ColorStats>,
+    pub power_sum: Option<u64>,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "games: {}", self.games)?;
+        writeln!(f, "draws: {}", self.draws)?;
+        for (color, stats) in &self.colors {
+            writeln!(
+                f,
+                "{color}: min {}, max {}, mean {:.2}",
+                stats.min, stats.max, stats.mean
+            )?;
+        }
+        writeln!(
+            f,
+            "power sum: {}",
+            power_field(self.power_sum, "does not fit into 64 bits")
+        )
+    }
+}
+
+// Every color of the input appears in the statistics of every game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub colors: Vec<String>,
+    pub games: Vec<GameStats>,
+    pub summary: Summary,
+}
+
+pub fn statistics(games: &[Game]) -> Statistics {
+    let colors = colors(games)
+        .into_iter()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let stats = games
+        .iter()
+        .map(|game| GameStats {
+            id: game.id,
+            draws: game.draws.clone(),
+            colors: ColorStats::per_color(&game.draws.iter().collect::<Vec<_>>(), &colors),
+            power: game
+                .lowest_config()
+                .power_set(colors.iter().map(String::as_str)),
+        })
+        .collect::<Vec<_>>();
+    let draws = games
+        .iter()
+        .flat_map(|game| &game.draws)
+        .collect::<Vec<_>>();
+    let summary = Summary {
+        games: games.len(),
+        draws: draws.len(),
+        colors: ColorStats::per_color(&draws, &colors),
+        power_sum: sum_powers(stats.iter().map(|game| game.power)),
+    };
+    Statistics {
+        colors,
+        games: stats,
+        summary,
+    }
+}
+
+// Powers that do not fit into 64 bits are written as `none`.
+fn power_field(power: Option<u64>, none: &str) -> String {
+    power.map_or_else(|| none.to_owned(), |power| power.to_string())
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_colors(colors: &BTreeMap<String, ColorStats>) -> String {
+    let fields = colors
+        .iter()
+        .map(|(color, stats)| {
+            format!(
+                "{}:{{\"min\":{},\"max\":{},\"mean\":{}}}",
+                json_string(color),
+                stats.min,
+                stats.max,
+                stats.mean
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn sorted(draw: &Draw) -> Vec<(&str, u32)> {
+    let mut counts = draw
+        .colors()
+        .map(|color| (color, draw.get(color)))
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+    counts
+}
+
+fn json_draw(draw: &Draw) -> String {
+    let fields = sorted(draw)
+        .into_iter()
+        .map(|(color, amount)| format!("{}:{amount}", json_string(color)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+// Draws written like the input, e.g. "3 blue, 4 red; 2 green".
+fn text_draws(draws: &[Draw]) -> String {
+    draws
+        .iter()
+        .map(|draw| {
+            sorted(draw)
+                .into_iter()
+                .map(|(color, amount)| format!("{amount} {color}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn csv_colors(colors: &BTreeMap<String, ColorStats>) -> impl Iterator<Item = String> + '_ {
+    colors.values().flat_map(|stats| {
+        [
+            stats.min.to_string(),
+            stats.max.to_string(),
+            stats.mean.to_string(),
+        ]
+    })
+}
+
+impl Statistics {
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let games = self
+            .games
+            .iter()
+            .map(|game| {
+                let draws = game.draws.iter().map(json_draw).collect::<Vec<_>>();
+                format!(
+                    "{{\"id\":{},\"draws\":[{}],\"colors\":{},\"power\":{}}}",
+                    game.id,
+                    draws.join(","),
+                    json_colors(&game.colors),
+                    power_field(game.power, "null")
+                )
+            })
+            .collect::<Vec<_>>();
+        writeln!(
+            writer,
+            "{{\"games\":[{}],\"summary\":{{\"games\":{},\"draws\":{},\"colors\":{},\"power_sum\":{}}}}}",
+            games.join(","),
+            self.summary.games,
+            self.summary.draws,
+            json_colors(&self.summary.colors),
+            power_field(self.summary.power_sum, "null")
+        )
+    }
+
+    fn csv_header(&self, first: [&str; 2], last: &str) -> String {
+        let mut header = first.map(str::to_owned).to_vec();
+        for color in &self.colors {
+            for column in ["min", "max", "mean"] {
+                header.push(csv_field(&format!("{color}_{column}")));
+            }
+        }
+        header.push(last.to_owned());
+        header.join(",")
+    }
+
+    // One row per game, with a min, max and mean column for every color.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", self.csv_header(["id", "draws"], "power"))?;
+        for game in &self.games {
+            let mut row = vec![game.id.to_string(), csv_field(&text_draws(&game.draws))];
+            row.extend(csv_colors(&game.colors));
+            row.push(power_field(game.power, ""));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    // The summary as a single row, with the same color columns as `write_csv`.
+    pub fn write_summary_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{}",
+            self.csv_header(["games", "draws"], "power_sum")
+        )?;
+        let mut row = vec![
+            self.summary.games.to_string(),
+            self.summary.draws.to_string(),
+        ];
+        row.extend(csv_colors(&self.summary.colors));
+        row.push(power_field(self.summary.power_sum, ""));
+        writeln!(writer, "{}", row.join(","))
+    }
+}
+
+#[test]
+fn test_statistics() {
+    let games = crate::parse_games("Game 1: 3 blue, 4 red; 2 blue\nGame 7: 1 red");
+    let statistics = statistics(&games);
+    assert_eq!(
+        statistics.games[0].colors["blue"],
+        ColorStats {
+            min: 2,
+            max: 3,
+            mean: 2.5
+        }
+    );
+    assert_eq!(statistics.summary.power_sum, Some(12));
+
+    let mut json = Vec::new();
+    statistics.write_json(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        concat!(
+            r#"{"games":["#,
+            r#"{"id":1,"draws":[{"blue":3,"red":4},{"blue":2}],"colors":{"blue":{"min":2,"max":3,"mean":2.5},"red":{"min":0,"max":4,"mean":2}},"power":12},"#,
+            r#"{"id":7,"draws":[{"red":1}],"colors":{"blue":{"min":0,"max":0,"mean":0},"red":{"min":1,"max":1,"mean":1}},"power":0}"#,
+            r#"],"summary":{"games":2,"draws":3,"colors":{"blue":{"min":0,"max":3,"mean":1.6666666666666667},"red":{"min":0,"max":4,"mean":1.6666666666666667}},"power_sum":12}}"#,
+            "\n"
+        )
+    );
+
+    let mut csv = Vec::new();
+    statistics.write_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "id,draws,blue_min,blue_max,blue_mean,red_min,red_max,red_mean,power\n\
+         1,\"3 blue, 4 red; 2 blue\",2,3,2.5,0,4,2,12\n\
+         7,1 red,0,0,0,1,1,1,0\n"
+    );
+    let mut csv = Vec::new();
+    statistics.write_summary_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "games,draws,blue_min,blue_max,blue_mean,red_min,red_max,red_mean,power_sum\n\
+         2,3,0,3,1.6666666666666667,0,4,1.6666666666666667,12\n"
+    );
+    assert_eq!(
+        statistics.summary.to_string(),
+        "games: 2\ndraws: 3\nblue: min 0, max 3, mean 1.67\nred: min 0, max 4, mean 1.67\npower sum: 12\n"
+    );
+
+    let games = crate::parse_games(
+        "Game 1: 100 red, 100 green, 100 blue, 100 teal, 100 purple
+        Game 2: 10000 red, 10000 green, 10000 blue, 10000 teal, 10000 purple",
+    );
+    let large = self::statistics(&games);
+    assert_eq!(large.games[0].power, Some(10_000_000_000));
+    assert_eq!(large.games[1].power, None);
+    assert_eq!(large.summary.power_sum, None);
+    let mut csv = Vec::new();
+    large.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.lines().nth(1).unwrap().ends_with(",10000000000"));
+    assert!(csv.lines().nth(2).unwrap().ends_with(",10000,10000,10000,"));
+    let mut json = Vec::new();
+    large.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains(r#""power":10000000000}"#));
+    assert!(json.contains(r#""power":null}"#));
+    assert!(json.ends_with("\"power_sum\":null}}\n"));
+    assert!(large
+        .summary
+        .to_string()
+        .ends_with("power sum: does not fit into 64 bits\n"));
+}
@@ -0,0 +1,234 @@
+use std::fmt::Display;
+
+use crate::{Board, Cell, Number};
+
+// Cells are given as 0-based (row, column) on the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditError {
+    OutOfBounds { row: usize, column: usize },
+    NumberTooLarge { row: usize, column: usize },
+    // Whitespace and control characters, which the schematic can not hold.
+    InvalidCharacter { row: usize, column: usize, x: char },
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditError::OutOfBounds { row, column } => {
+                write!(f, "cell ({row}, {column}) is not on the board")
+            }
+            EditError::NumberTooLarge { row, column } => write!(
+                f,
+                "the number at ({row}, {column}) does not fit into 32 bits"
+            ),
+            EditError::InvalidCharacter { row, column, x } => {
+                write!(f, "{x:?} can not be put at ({row}, {column})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl Board {
+    // Part 1, computed on first use and kept up to date by `edit`.
+    pub fn part_sum(&self) -> i64 {
+        *self.part_sum.get_or_init(|| self.compute_sum())
+    }
+
+    // Part 2, computed on first use and kept up to date by `edit`.
+    pub fn gear_sum(&self) -> i64 {
+        *self.gear_sum.get_or_init(|| self.compute_engine_sum())
+    }
+
+    // Part and gear totals of only the given numbers and symbol cells.
+    fn local_totals(&self, ids: &[usize], cells: &[(usize, usize)]) -> (i64, i64) {
+        let parts = ids
+            .iter()
+            .map(|&id| &self.numbers[id])
+            .filter(|number| self.is_part(number))
+            .map(|number| i64::from(number.value))
+            .sum();
+        let gears = cells
+            .iter()
+            .filter_map(|&(row, column)| match self.cells[row][column] {
+                Cell::Symbol(symbol) => {
+                    let ids = self.adjacent_numbers(row, column);
+                    let is_gear = self.gear_rule.is_gear(symbol, ids.len());
+                    is_gear.then(|| self.gear_ratio(&ids))
+                }
+                _ => None,
+            })
+            .sum();
+        (parts, gears)
+    }
+
+    // Puts a digit, a symbol or a blank ('.') at (row, column). Only the
+    // run of digits through the cell is parsed again, and totals are only
+    // updated for the numbers of that run, the numbers reaching the cell and
+    // the symbols they reach. Ids of later numbers shift when numbers are
+    // split or joined. On error the board is left unchanged.
+    pub fn edit(&mut self, row: usize, column: usize, x: char) -> Result<(), EditError> {
+        if self.cell(row, column).is_none() {
+            return Err(EditError::OutOfBounds { row, column });
+        }
+        if x.is_whitespace() || x.is_control() {
+            return Err(EditError::InvalidCharacter { row, column, x });
+        }
+        let is_number = |c: usize| matches!(self.cell(row, c), Some(Cell::Number(_)));
+        let mut start = column;
+        while start > 0 && is_number(start - 1) {
+            start -= 1;
+        }
+        let mut end = column + 1;
+        while is_number(end) {
+            end += 1;
+        }
+
+        let first = self
+            .numbers
+            .partition_point(|number| (number.row, number.columns.start) < (row, start));
+        let mut numbers = Vec::<Number>::new();
+        let mut cells = Vec::new();
+        for c in start..end {
+            let x = if c == column {
+                x
+            } else {
+                self.char_at(row, c).unwrap()
+            };
+            if let Some(digit) = x.to_digit(10) {
+                if let Some(Cell::Number(_)) = cells.last() {
+                    let number = numbers.last_mut().unwrap();
+                    number.value = number
+                        .value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit as i32))
+                        .ok_or(EditError::NumberTooLarge {
+                            row,
+                            column: number.columns.start,
+                        })?;
+                    number.columns.end = c + 1;
+                } else {
+                    numbers.push(Number {
+                        id: first + numbers.len(),
+                        value: digit as i32,
+                        row,
+                        columns: c..c + 1,
+                    });
+                }
+                cells.push(Cell::Number(first + numbers.len() - 1));
+            } else if x == '.' {
+                cells.push(Cell::Empty);
+            } else {
+                cells.push(Cell::Symbol(x));
+            }
+        }
+
+        let old = self.numbers[first..]
+            .iter()
+            .take_while(|number| number.row == row && number.columns.start < end)
+            .count();
+        let mut reached = (start..end)
+            .flat_map(|c| self.neighbors(row, c, false))
+            .chain([(row, column)])
+            .collect::<Vec<_>>();
+        reached.sort_unstable();
+        reached.dedup();
+        let affected = |board: &Board, count: usize| {
+            let mut ids = (first..first + count)
+                .chain(board.adjacent_numbers(row, column))
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids.dedup();
+            board.local_totals(&ids, &reached)
+        };
+        let (parts, gears) = affected(self, old);
+
+        let new = numbers.len();
+        self.numbers.splice(first..first + old, numbers);
+        self.cells[row].splice(start..end, cells);
+        if new != old {
+            for id in first + new..self.numbers.len() {
+                let number = &mut self.numbers[id];
+                number.id = id;
+                for c in number.columns.clone() {
+                    self.cells[number.row][c] = Cell::Number(id);
+                }
+            }
+        }
+
+        let (new_parts, new_gears) = affected(self, new);
+        if let Some(sum) = self.part_sum.get_mut() {
+            *sum += new_parts - parts;
+        }
+        if let Some(sum) = self.gear_sum.get_mut() {
+            *sum += new_gears - gears;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_edits() {
+    use std::str::FromStr;
+
+    let mut board = Board::from_str(crate::EXAMPLE).unwrap();
+    assert_eq!((board.part_sum(), board.gear_sum()), (4361, 467835));
+    board.edit(0, 3, '5').unwrap();
+    assert_eq!(board.numbers()[0].value, 4675);
+    assert_eq!(
+        (board.part_sum(), board.gear_sum()),
+        (4361 - 467 + 4675, 467835 - 467 * 35 + 4675 * 35)
+    );
+    assert_eq!(
+        board.edit(10, 0, '1'),
+        Err(EditError::OutOfBounds { row: 10, column: 0 })
+    );
+    for x in [' ', '\n', '\t', '\u{7}'] {
+        assert_eq!(
+            board.edit(1, 3, x),
+            Err(EditError::InvalidCharacter {
+                row: 1,
+                column: 3,
+                x
+            })
+        );
+    }
+    assert_eq!(board.char_at(1, 3), Some('*'));
+
+    // Random edits, checked against a full recomputation and a fresh parse.
+    let mut state = 42u64;
+    let mut next = |n: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % n
+    };
+    let alphabet = "0123456789.....*#$+ ".chars().collect::<Vec<_>>();
+    for _ in 0..2000 {
+        let (row, column, x) = (next(10), next(10), alphabet[next(alphabet.len())]);
+        if board.edit(row, column, x).is_err() {
+            continue;
+        }
+        assert_eq!(board.part_sum(), board.compute_sum());
+        assert_eq!(board.gear_sum(), board.compute_engine_sum());
+        assert_eq!(
+            board.numbers(),
+            Board::from_str(&board.to_string()).unwrap().numbers()
+        );
+    }
+
+    let mut board = Board::from_str("111111111.1*").unwrap();
+    assert_eq!(
+        board.edit(0, 9, '1'),
+        Err(EditError::NumberTooLarge { row: 0, column: 0 })
+    );
+    assert_eq!(board.to_string(), "111111111.1*");
+    assert_eq!(board.part_sum(), 1);
+
+    // Parsing computes no totals, and the totals fit large gear ratios.
+    let mut board = Board::from_str("99999*99999").unwrap();
+    assert_eq!(board.gear_sum(), 99999 * 99999);
+    board.edit(0, 0, '.').unwrap();
+    assert_eq!(board.gear_sum(), 9999 * 99999);
+}
@@ -0,0 +1,108 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+// How the adjacent part numbers of a gear make up its ratio.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Combine {
+    #[default]
+    Product,
+    Sum,
+    Max,
+}
+
+// Which symbols are gears and how their ratio is computed. The default is the
+// puzzle's rule: a '*' next to exactly two parts, whose product is the ratio.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRule {
+    symbols: Vec<char>,
+    parts: PartCount,
+    combine: Combine,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self::new(['*'])
+    }
+}
+
+impl GearRule {
+    pub fn new(symbols: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            symbols: symbols.into_iter().collect(),
+            parts: PartCount::Exactly(2),
+            combine: Combine::default(),
+        }
+    }
+
+    pub fn with_parts(mut self, parts: PartCount) -> Self {
+        self.parts = parts;
+        self
+    }
+
+    pub fn with_combine(mut self, combine: Combine) -> Self {
+        self.combine = combine;
+        self
+    }
+
+    // A symbol without parts is never a gear, even for `Exactly(0)` or
+    // `AtLeast(0)`, as it has no ratio.
+    pub fn is_gear(&self, symbol: char, parts: usize) -> bool {
+        parts > 0
+            && self.symbols.contains(&symbol)
+            && match self.parts {
+                PartCount::Exactly(count) => parts == count,
+                PartCount::AtLeast(count) => parts >= count,
+            }
+    }
+
+    pub fn ratio(&self, values: impl Iterator<Item = i32>) -> i64 {
+        let values = values.map(i64::from);
+        match self.combine {
+            Combine::Product => values.product(),
+            Combine::Sum => values.sum(),
+            Combine::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+#[test]
+fn test_gear_rules() {
+    use crate::Board;
+    use std::str::FromStr;
+
+    let board = || Board::from_str(crate::EXAMPLE).unwrap();
+    assert_eq!(board().compute_engine_sum(), 467835);
+    let rule = GearRule::default()
+        .with_parts(PartCount::AtLeast(1))
+        .with_combine(Combine::Sum);
+    assert_eq!(board().with_gear_rule(rule).compute_engine_sum(), 2472);
+    let rule = GearRule::default()
+        .with_parts(PartCount::Exactly(1))
+        .with_combine(Combine::Max);
+    assert_eq!(board().with_gear_rule(rule).compute_engine_sum(), 617);
+    let rule = GearRule::new(['*', '#', '+', '$'])
+        .with_parts(PartCount::AtLeast(1))
+        .with_combine(Combine::Max);
+    assert_eq!(
+        board().with_gear_rule(rule).compute_engine_sum(),
+        467 + 617 + 755 + 633 + 592 + 664
+    );
+
+    let board = Board::from_str("1.2\n.*.\n..3").unwrap();
+    assert_eq!(board.compute_engine_sum(), 0);
+    let rule = GearRule::default().with_parts(PartCount::AtLeast(2));
+    assert_eq!(board.with_gear_rule(rule.clone()).compute_engine_sum(), 6);
+    assert_eq!(
+        crate::Day3Solver::solve_with_rule("1.2\n.*.\n..3", &rule),
+        Ok(6)
+    );
+
+    let board = || Board::from_str("..*..\n7*...").unwrap();
+    let rule = GearRule::default().with_parts(PartCount::AtLeast(0));
+    assert_eq!(board().with_gear_rule(rule).compute_engine_sum(), 7);
+    let rule = GearRule::default().with_parts(PartCount::Exactly(0));
+    assert_eq!(board().with_gear_rule(rule).gears(), vec![]);
+}
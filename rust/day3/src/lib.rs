@@ -1,183 +1,249 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{cell::OnceCell, fmt::Debug, ops::Range, str::FromStr};
 
 use aoc_traits::AdventOfCodeDay;
 
+mod edit;
+mod gear;
+mod neighborhood;
+mod parser;
+mod queries;
+mod render;
+mod symbols;
+
+pub use edit::EditError;
+pub use gear::{Combine, GearRule, PartCount};
+pub use neighborhood::Neighborhood;
+pub use parser::{BoardError, RaggedRows};
+pub use queries::Group;
+pub use symbols::SymbolStats;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    // Part of the number with this id.
+    Number(usize),
+    Symbol(char),
+}
+
+// A number of the schematic. Equal values at different places are different
+// numbers, so they are told apart by `id`, which indexes `Board::numbers`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Number {
+    pub id: usize,
+    pub value: i32,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
 #[derive(Debug)]
 pub struct Board {
-    field: Vec<Vec<i32>>,
+    cells: Vec<Vec<Cell>>,
+    numbers: Vec<Number>,
+    neighborhood: Neighborhood,
+    offsets: Vec<(isize, isize)>,
+    gear_rule: GearRule,
+    // Filled on first use and then kept up to date by `edit`.
+    part_sum: OnceCell<i64>,
+    gear_sum: OnceCell<i64>,
 }
 
 impl Board {
-    fn compute_sum(&self) -> i32 {
-        let mut sum = 0;
-        let mut already_added = false;
-        for i in 0..self.field.len() {
-            let current_row = self.field.get(i).unwrap();
-            for j in 0..current_row.len() {
-                if *current_row.get(j).unwrap() > 0 {
-                    if !already_added
-                        && (self.field[i][j + 1] <= -1
-                            || self.field[i + 1][j] <= -1
-                            || self.field[i + 1][j + 1] <= -1
-                            || self.field[i + 1][j - 1] <= -1
-                            || self.field[i - 1][j] <= -1
-                            || self.field[i][j - 1] <= -1
-                            || self.field[i - 1][j - 1] <= -1
-                            || self.field[i - 1][j + 1] <= -1)
-                    {
-                        sum += current_row.get(j).unwrap();
-                        already_added = true;
-                    }
-                } else {
-                    already_added = false;
-                }
-            }
-        }
-        sum
+    pub fn cell(&self, row: usize, column: usize) -> Option<Cell> {
+        self.cells.get(row)?.get(column).copied()
     }
-    fn compute_engine_sum(&self) -> i32 {
-        let mut sum = 0;
-        for i in 0..self.field.len() {
-            let current_row = self.field.get(i).unwrap();
-            for (j, char) in current_row.iter().enumerate() {
-                if *char == -2 {
-                    //look for two numbers
-                    let mut factors = Vec::new();
-                    let mut x1 = 0;
-                    if self.field[i - 1][j - 1] > 0 {
-                        x1 = self.field[i - 1][j - 1];
-                        factors.push(x1);
-                    }
-                    if self.field[i - 1][j] > 0 && x1 != self.field[i - 1][j] {
-                        x1 = self.field[i - 1][j];
-                        factors.push(x1);
-                    } else if self.field[i - 1][j + 1] > 0 && x1 != self.field[i - 1][j + 1] {
-                        factors.push(self.field[i - 1][j + 1]);
-                    }
-                    if self.field[i][j - 1] > 0 {
-                        factors.push(self.field[i][j - 1])
-                    }
-                    if self.field[i][j + 1] > 0 {
-                        factors.push(self.field[i][j + 1])
-                    }
-                    x1 = 0;
-                    if self.field[i + 1][j - 1] > 0 {
-                        x1 = self.field[i + 1][j - 1];
-                        factors.push(x1);
-                    }
-                    if self.field[i + 1][j] > 0 && x1 != self.field[i + 1][j] {
-                        x1 = self.field[i + 1][j];
-                        factors.push(x1);
-                    } else if self.field[i + 1][j + 1] > 0 && x1 != self.field[i + 1][j + 1] {
-                        factors.push(self.field[i + 1][j + 1]);
-                    }
-                    if factors.len() > 1 {
-                        sum += factors.iter().product::<i32>();
-                    }
-                }
+
+    // The character the cell stands for in the schematic.
+    pub fn char_at(&self, row: usize, column: usize) -> Option<char> {
+        Some(match self.cell(row, column)? {
+            Cell::Empty => '.',
+            Cell::Symbol(symbol) => symbol,
+            Cell::Number(id) => {
+                let number = &self.numbers[id];
+                let width = number.columns.len();
+                let digits = format!("{:0width$}", number.value);
+                char::from(digits.as_bytes()[column - number.columns.start])
             }
-        }
-        sum
+        })
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter_map(move |(column, cell)| match cell {
+                    Cell::Symbol(symbol) => Some((row, column, *symbol)),
+                    _ => None,
+                })
+        })
+    }
+
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.offsets = neighborhood.offsets();
+        self.neighborhood = neighborhood;
+        self.part_sum = OnceCell::new();
+        self.gear_sum = OnceCell::new();
+        self
+    }
+
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    pub fn with_gear_rule(mut self, gear_rule: GearRule) -> Self {
+        self.gear_rule = gear_rule;
+        self.gear_sum = OnceCell::new();
+        self
+    }
+
+    pub fn gear_rule(&self) -> &GearRule {
+        &self.gear_rule
+    }
+
+    // The cells a number cell at (row, column) touches, or with `reverse` the
+    // number cells that touch (row, column). Cells off the board are skipped.
+    fn neighbors(
+        &self,
+        row: usize,
+        column: usize,
+        reverse: bool,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let sign = if reverse { -1 } else { 1 };
+        self.offsets.iter().filter_map(move |&(dr, dc)| {
+            let row = row.checked_add_signed(sign * dr)?;
+            let column = column.checked_add_signed(sign * dc)?;
+            self.cell(row, column).map(|_| (row, column))
+        })
+    }
+
+    pub fn adjacent_symbols(&self, number: &Number) -> Vec<(usize, usize, char)> {
+        let mut symbols = number
+            .columns
+            .clone()
+            .flat_map(|column| self.neighbors(number.row, column, false))
+            .filter_map(|(row, column)| match self.cells[row][column] {
+                Cell::Symbol(symbol) => Some((row, column, symbol)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        symbols.sort_unstable();
+        symbols.dedup();
+        symbols
+    }
+
+    // Ids of the distinct numbers around (row, column).
+    pub fn adjacent_numbers(&self, row: usize, column: usize) -> Vec<usize> {
+        let mut ids = self
+            .neighbors(row, column, true)
+            .filter_map(|(row, column)| match self.cells[row][column] {
+                Cell::Number(id) => Some(id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    pub fn is_part(&self, number: &Number) -> bool {
+        !self.adjacent_symbols(number).is_empty()
+    }
+
+    pub fn compute_sum(&self) -> i64 {
+        self.numbers
+            .iter()
+            .filter(|number| self.is_part(number))
+            .map(|number| i64::from(number.value))
+            .sum()
+    }
+
+    // The symbols that are gears under the gear rule, as (row, column,
+    // number ids).
+    pub fn gears(&self) -> Vec<(usize, usize, Vec<usize>)> {
+        self.symbols()
+            .map(|(row, column, symbol)| (row, column, symbol, self.adjacent_numbers(row, column)))
+            .filter(|(_, _, symbol, ids)| self.gear_rule.is_gear(*symbol, ids.len()))
+            .map(|(row, column, _, ids)| (row, column, ids))
+            .collect()
+    }
+
+    pub fn gear_ratio(&self, ids: &[usize]) -> i64 {
+        self.gear_rule
+            .ratio(ids.iter().map(|&id| self.numbers[id].value))
+    }
+
+    pub fn compute_engine_sum(&self) -> i64 {
+        self.gears()
+            .iter()
+            .map(|(_, _, ids)| self.gear_ratio(ids))
+            .sum()
     }
 }
 
 impl FromStr for Board {
-    type Err = ();
+    type Err = BoardError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut field = s
-            .trim()
-            .lines()
-            .map(|s| s.trim().chars())
-            .map(|mut chars| {
-                let mut current_row = Vec::new();
-                current_row.push(0);
-                let mut parsed_string = String::new();
-                loop {
-                    match chars.next() {
-                        Some(x) if x.is_ascii_digit() => parsed_string.push(x),
-                        Some(x) => {
-                            if !parsed_string.is_empty() {
-                                current_row.extend(vec![
-                                    parsed_string.parse::<i32>().unwrap();
-                                    parsed_string.len()
-                                ]);
-                                parsed_string.clear();
-                            }
-                            if x == '.' {
-                                current_row.push(0);
-                            } else if x == '*' {
-                                current_row.push(-2);
-                            } else {
-                                current_row.push(-1);
-                            }
-                        }
-                        None => {
-                            if !parsed_string.is_empty() {
-                                current_row.extend(vec![
-                                    parsed_string.parse::<i32>().unwrap();
-                                    parsed_string.len()
-                                ]);
-                                parsed_string.clear();
-                            }
-                            break;
-                        }
-                    }
-                }
-                current_row.push(0);
-                current_row
-            })
-            .collect::<Vec<Vec<i32>>>();
-        field.push(vec![0; field[0].len()]);
-        let mut extended_field = vec![vec![0; field[0].len()]];
-        extended_field.extend(field);
-        Ok(Self {
-            field: extended_field,
-        })
+        Board::parse(s, RaggedRows::Reject)
     }
 }
 
 #[cfg(test)]
-fn day3(input: &str) -> (i32, i32) {
+fn day3(input: &str) -> (i64, i64) {
     let board = Board::from_str(input).unwrap();
     (board.compute_sum(), board.compute_engine_sum())
 }
 
 pub struct Day3Solver;
 
+impl Day3Solver {
+    // Part 2 under a different definition of gears.
+    pub fn solve_with_rule(input: &str, rule: &GearRule) -> Result<i64, BoardError> {
+        Ok(Board::from_str(input)?
+            .with_gear_rule(rule.clone())
+            .gear_sum())
+    }
+}
+
 impl<'a> AdventOfCodeDay<'a> for Day3Solver {
     type ParsedInput = Board;
 
-    type Part1Output = i32;
+    type Part1Output = i64;
 
-    type Part2Output = i32;
+    type Part2Output = i64;
 
     fn solve_part1(input: &Self::ParsedInput) -> Self::Part1Output {
-        input.compute_sum()
+        input.part_sum()
     }
 
     fn solve_part2(input: &Self::ParsedInput) -> Self::Part2Output {
-        input.compute_engine_sum()
+        input.gear_sum()
     }
 
     fn parse_input(input: &'a str) -> Self::ParsedInput {
-        Board::from_str(input).unwrap()
+        Board::from_str(input).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
+#[cfg(test)]
+const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
 #[test]
 fn test_example() {
-    let input = "467..114..
-    ...*......
-    ..35..633.
-    ......#...
-    617*......
-    .....+.58.
-    ..592.....
-    ......755.
-    ...$.*....
-    .664.598..";
-    assert_eq!(day3(input), (4361, 467835));
+    assert_eq!(day3(EXAMPLE), (4361, 467835));
 }
 
 #[test]
@@ -325,3 +391,36 @@ fn my_challenge_1() {
 .............................87...622..........................822...............................................215.............810........";
     assert_eq!((539713, 84159075), day3(input));
 }
+
+#[test]
+fn test_equal_numbers() {
+    let board = Board::from_str("12.12\n..*..\n.12..").unwrap();
+    assert_eq!(board.numbers().len(), 3);
+    assert_eq!(board.numbers()[1].columns, 3..5);
+    assert_eq!(board.adjacent_numbers(1, 2), vec![0, 1, 2]);
+    assert_eq!(day3("12.12\n..*.."), (24, 144));
+}
+
+#[test]
+fn test_neighborhoods() {
+    let board = Board::from_str(EXAMPLE).unwrap();
+    assert_eq!(board.compute_sum(), 4361);
+    let board = board.with_neighborhood(Neighborhood::FourConnected);
+    assert_eq!(board.compute_sum(), 35 + 633 + 617 + 664 + 598);
+    assert_eq!(board.compute_engine_sum(), 0);
+    let board = board.with_neighborhood(Neighborhood::Chebyshev(1));
+    assert_eq!(
+        (board.compute_sum(), board.compute_engine_sum()),
+        (4361, 467835)
+    );
+
+    let board = Board::from_str("1.*.2").unwrap();
+    assert_eq!(board.compute_sum(), 0);
+    let board = board.with_neighborhood(Neighborhood::Chebyshev(2));
+    assert_eq!((board.compute_sum(), board.compute_engine_sum()), (3, 2));
+
+    let board = Board::from_str("1*2").unwrap();
+    let board = board.with_neighborhood(Neighborhood::Stencil(vec![(0, 1)]));
+    assert_eq!(board.compute_sum(), 1);
+    assert_eq!(board.adjacent_numbers(0, 1), vec![0]);
+}
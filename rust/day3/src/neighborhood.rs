@@ -0,0 +1,33 @@
+// Which cells count as touching. Offsets are (rows, columns) from a cell of a
+// number to the cells where a symbol makes it a part, so a custom stencil
+// does not have to be symmetric.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Neighborhood {
+    FourConnected,
+    #[default]
+    EightConnected,
+    // Every cell within this many steps, diagonals included.
+    Chebyshev(usize),
+    Stencil(Vec<(isize, isize)>),
+}
+
+impl Neighborhood {
+    // The offsets without (0, 0), since a cell does not touch itself.
+    pub fn offsets(&self) -> Vec<(isize, isize)> {
+        let square = |radius: isize| {
+            (-radius..=radius)
+                .flat_map(move |dr| (-radius..=radius).map(move |dc| (dr, dc)))
+                .collect::<Vec<_>>()
+        };
+        let mut offsets = match self {
+            Neighborhood::FourConnected => vec![(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighborhood::EightConnected => square(1),
+            Neighborhood::Chebyshev(radius) => square(*radius as isize),
+            Neighborhood::Stencil(offsets) => offsets.clone(),
+        };
+        offsets.sort_unstable();
+        offsets.dedup();
+        offsets.retain(|&offset| offset != (0, 0));
+        offsets
+    }
+}
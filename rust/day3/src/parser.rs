@@ -0,0 +1,160 @@
+use std::{cell::OnceCell, fmt::Display};
+
+use crate::{Board, Cell, GearRule, Neighborhood, Number};
+
+// What to do with rows that are shorter or longer than the others.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    // Fail on the first row whose width differs from the first row.
+    #[default]
+    Reject,
+    // Fill short rows with empty cells up to the widest row.
+    Pad,
+}
+
+// Lines and columns are 1-based and count from the start of the input, so
+// they match an editor. Columns count characters, including the indentation
+// of the row. Widths count the cells of a row, without its indentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    NumberTooLarge {
+        line: usize,
+        column: usize,
+    },
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(f, "line {line} has {width} cells, expected {expected}"),
+            BoardError::NumberTooLarge { line, column } => write!(
+                f,
+                "line {line}, column {column}: number does not fit into 32 bits"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+impl Board {
+    // Surrounding whitespace of every row and blank lines before and after
+    // the schematic are ignored.
+    pub fn parse(s: &str, ragged: RaggedRows) -> Result<Self, BoardError> {
+        let mut lines = s
+            .lines()
+            .zip(1..)
+            .map(|(text, line)| {
+                let indent = text.chars().take_while(|c| c.is_whitespace()).count();
+                (text.trim(), line, indent)
+            })
+            .collect::<Vec<_>>();
+        while lines.last().is_some_and(|(text, _, _)| text.is_empty()) {
+            lines.pop();
+        }
+        let first = lines.iter().position(|(text, _, _)| !text.is_empty());
+        let lines = &lines[first.unwrap_or(lines.len())..];
+
+        let mut cells = Vec::new();
+        let mut numbers = Vec::<Number>::new();
+        for (row, &(text, line, indent)) in lines.iter().enumerate() {
+            let mut current_row = Vec::new();
+            for (column, x) in text.chars().enumerate() {
+                if let Some(digit) = x.to_digit(10) {
+                    if let Some(&Cell::Number(id)) = current_row.last() {
+                        let number = &mut numbers[id];
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(digit as i32))
+                            .ok_or(BoardError::NumberTooLarge {
+                                line,
+                                column: indent + number.columns.start + 1,
+                            })?;
+                        number.columns.end = column + 1;
+                    } else {
+                        numbers.push(Number {
+                            id: numbers.len(),
+                            value: digit as i32,
+                            row,
+                            columns: column..column + 1,
+                        });
+                    }
+                    current_row.push(Cell::Number(numbers.len() - 1));
+                } else if x == '.' {
+                    current_row.push(Cell::Empty);
+                } else {
+                    current_row.push(Cell::Symbol(x));
+                }
+            }
+            cells.push(current_row);
+        }
+
+        let widths = cells.iter().map(Vec::len);
+        match ragged {
+            RaggedRows::Reject => {
+                let expected = cells.first().map_or(0, Vec::len);
+                if let Some((width, &(_, line, _))) =
+                    widths.zip(lines).find(|&(width, _)| width != expected)
+                {
+                    return Err(BoardError::Ragged {
+                        line,
+                        width,
+                        expected,
+                    });
+                }
+            }
+            RaggedRows::Pad => {
+                let width = widths.max().unwrap_or(0);
+                for row in &mut cells {
+                    row.resize(width, Cell::Empty);
+                }
+            }
+        }
+        Ok(Self {
+            cells,
+            numbers,
+            neighborhood: Neighborhood::default(),
+            offsets: Neighborhood::default().offsets(),
+            gear_rule: GearRule::default(),
+            part_sum: OnceCell::new(),
+            gear_sum: OnceCell::new(),
+        })
+    }
+}
+
+#[test]
+fn test_ragged_rows() {
+    let input = "\n467..114..\n...*......\n..35..\n";
+    assert_eq!(
+        Board::parse(input, RaggedRows::Reject).unwrap_err(),
+        BoardError::Ragged {
+            line: 4,
+            width: 6,
+            expected: 10
+        }
+    );
+    let board = Board::parse(input, RaggedRows::Pad).unwrap();
+    assert_eq!(board.cell(2, 9), Some(Cell::Empty));
+    assert_eq!(board.compute_sum(), 467 + 35);
+
+    let board = Board::parse("..35\n..*.\n467.....", RaggedRows::Pad).unwrap();
+    assert_eq!(board.compute_engine_sum(), 35 * 467);
+
+    let error = Board::parse("1.\n.99999999999", RaggedRows::Pad).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 2: number does not fit into 32 bits"
+    );
+    let error = Board::parse("  1.\n  .99999999999", RaggedRows::Pad).unwrap_err();
+    assert_eq!(error, BoardError::NumberTooLarge { line: 2, column: 4 });
+}
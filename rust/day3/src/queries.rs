@@ -0,0 +1,138 @@
+use std::{collections::HashMap, ops::Range};
+
+use crate::{Board, Cell};
+
+// Numbers and symbols linked by touching, directly or through each other.
+// Two numbers are only connected through a symbol they both touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Group {
+    pub numbers: Vec<usize>,
+    pub symbols: Vec<(usize, usize)>,
+}
+
+fn find(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+impl Board {
+    // Ids of the part numbers around (row, column), without the number the
+    // cell itself belongs to.
+    pub fn parts_touching(&self, row: usize, column: usize) -> Vec<usize> {
+        let own = match self.cell(row, column) {
+            Some(Cell::Number(id)) => Some(id),
+            _ => None,
+        };
+        self.adjacent_numbers(row, column)
+            .into_iter()
+            .filter(|&id| Some(id) != own && self.is_part(&self.numbers[id]))
+            .collect()
+    }
+
+    // `None` for an unknown id. Ids shift when an edit splits or joins
+    // numbers, so callers may hold on to stale ones.
+    pub fn symbols_touching(&self, id: usize) -> Option<Vec<(usize, usize, char)>> {
+        Some(self.adjacent_symbols(self.numbers.get(id)?))
+    }
+
+    // Ids of the numbers lying completely inside the rectangle.
+    pub fn numbers_in(&self, rows: Range<usize>, columns: Range<usize>) -> Vec<usize> {
+        // Numbers are stored in reading order, so the rows are contiguous.
+        let start = self
+            .numbers
+            .partition_point(|number| number.row < rows.start);
+        self.numbers[start..]
+            .iter()
+            .take_while(|number| number.row < rows.end)
+            .filter(|number| {
+                columns.start <= number.columns.start && number.columns.end <= columns.end
+            })
+            .map(|number| number.id)
+            .collect()
+    }
+
+    // Every number and symbol ends up in exactly one group, ordered by their
+    // first cell in reading order.
+    pub fn groups(&self) -> Vec<Group> {
+        let symbols = self
+            .symbols()
+            .map(|(row, column, _)| (row, column))
+            .collect::<Vec<_>>();
+        let numbers = self.numbers.len();
+        let mut parents = (0..numbers + symbols.len()).collect::<Vec<_>>();
+        for (i, &(row, column)) in symbols.iter().enumerate() {
+            for id in self.adjacent_numbers(row, column) {
+                let (a, b) = (find(&mut parents, numbers + i), find(&mut parents, id));
+                parents[a] = b;
+            }
+        }
+
+        let mut nodes = (0..numbers)
+            .map(|id| ((self.numbers[id].row, self.numbers[id].columns.start), id))
+            .chain(
+                symbols
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &cell)| (cell, numbers + i)),
+            )
+            .collect::<Vec<_>>();
+        nodes.sort_unstable();
+        let mut index = HashMap::new();
+        let mut groups = Vec::<Group>::new();
+        for (_, node) in nodes {
+            let root = find(&mut parents, node);
+            let group = *index.entry(root).or_insert_with(|| {
+                groups.push(Group::default());
+                groups.len() - 1
+            });
+            if node < numbers {
+                groups[group].numbers.push(node);
+            } else {
+                groups[group].symbols.push(symbols[node - numbers]);
+            }
+        }
+        groups
+    }
+}
+
+#[test]
+fn test_queries() {
+    use std::str::FromStr;
+
+    let board = Board::from_str(crate::EXAMPLE).unwrap();
+    assert_eq!(board.parts_touching(1, 2), vec![0, 2]);
+    assert_eq!(board.parts_touching(1, 7), vec![3]);
+    assert_eq!(board.parts_touching(6, 8), vec![7]);
+    assert_eq!(board.parts_touching(2, 3), vec![]);
+    assert_eq!(board.symbols_touching(7), Some(vec![(8, 5, '*')]));
+    assert_eq!(board.symbols_touching(10), None);
+    assert_eq!(board.numbers_in(0..3, 0..4), vec![0, 2]);
+    assert_eq!(board.numbers_in(5..10, 5..9), vec![5, 7, 9]);
+
+    let groups = board.groups();
+    assert_eq!(groups.len(), 8);
+    assert_eq!(
+        groups[0],
+        Group {
+            numbers: vec![0, 2],
+            symbols: vec![(1, 3)]
+        }
+    );
+    assert_eq!(
+        groups[1],
+        Group {
+            numbers: vec![1],
+            symbols: vec![]
+        }
+    );
+    assert_eq!(
+        groups
+            .iter()
+            .map(|group| group.numbers.len())
+            .sum::<usize>(),
+        board.numbers().len()
+    );
+}
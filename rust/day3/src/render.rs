@@ -0,0 +1,98 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+};
+
+use crate::{Board, Cell};
+
+const PART: &str = "\x1b[32m";
+const IGNORED: &str = "\x1b[90m";
+const FACTOR: &str = "\x1b[33m";
+const TOUCHING: &str = "\x1b[36m";
+const ISOLATED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+impl Board {
+    // The schematic with numbers colored as gear factors (yellow), other
+    // parts (green) or ignored numbers (gray), and symbols colored by whether
+    // they touch a part (cyan) or not (red). Rows with gears end in the gear
+    // columns and ratios.
+    pub fn render(&self) -> String {
+        let gears = self.gears();
+        let factors = gears
+            .iter()
+            .flat_map(|(_, _, ids)| ids.iter().copied())
+            .collect::<HashSet<_>>();
+        let mut out = String::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                match *cell {
+                    Cell::Empty => out.push('.'),
+                    Cell::Number(id) => {
+                        let number = &self.numbers[id];
+                        if number.columns.start != column {
+                            continue;
+                        }
+                        let color = if factors.contains(&id) {
+                            FACTOR
+                        } else if self.is_part(number) {
+                            PART
+                        } else {
+                            IGNORED
+                        };
+                        // Padding restores leading zeros.
+                        let width = number.columns.len();
+                        write!(out, "{color}{:0width$}{RESET}", number.value).unwrap();
+                    }
+                    Cell::Symbol(symbol) => {
+                        let color = if self.adjacent_numbers(row, column).is_empty() {
+                            ISOLATED
+                        } else {
+                            TOUCHING
+                        };
+                        write!(out, "{color}{symbol}{RESET}").unwrap();
+                    }
+                }
+            }
+            let labels = gears
+                .iter()
+                .filter(|(gear_row, _, _)| *gear_row == row)
+                .map(|(_, column, ids)| format!("gear at {column}: {}", self.gear_ratio(ids)))
+                .collect::<Vec<_>>();
+            if !labels.is_empty() {
+                write!(out, "  {}", labels.join(", ")).unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// The plain schematic, which parses back into the same board.
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (row, cells) in self.cells.iter().enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for column in 0..cells.len() {
+                write!(f, "{}", self.char_at(row, column).unwrap())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_render() {
+    use std::str::FromStr;
+
+    let board = Board::from_str("12.12.\n..*...\n5....&\n007*..").unwrap();
+    assert_eq!(
+        board.render(),
+        "\x1b[33m12\x1b[0m.\x1b[33m12\x1b[0m.\n\
+         ..\x1b[36m*\x1b[0m...  gear at 2: 144\n\
+         \x1b[90m5\x1b[0m....\x1b[31m&\x1b[0m\n\
+         \x1b[32m007\x1b[0m\x1b[36m*\x1b[0m..\n"
+    );
+}
@@ -0,0 +1,68 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Board;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolStats {
+    pub occurrences: usize,
+    // Distinct numbers next to at least one occurrence of the symbol.
+    pub parts: usize,
+    pub part_sum: i64,
+}
+
+impl Board {
+    pub fn symbol_stats(&self) -> BTreeMap<char, SymbolStats> {
+        let mut parts = BTreeMap::<char, BTreeSet<usize>>::new();
+        let mut stats = BTreeMap::<char, SymbolStats>::new();
+        for (row, column, symbol) in self.symbols() {
+            stats.entry(symbol).or_default().occurrences += 1;
+            parts
+                .entry(symbol)
+                .or_default()
+                .extend(self.adjacent_numbers(row, column));
+        }
+        for (symbol, ids) in parts {
+            let stats = stats.get_mut(&symbol).unwrap();
+            stats.parts = ids.len();
+            stats.part_sum = ids
+                .iter()
+                .map(|&id| i64::from(self.numbers()[id].value))
+                .sum();
+        }
+        stats
+    }
+
+    // Symbols without any number around them.
+    pub fn isolated_symbols(&self) -> Vec<(usize, usize, char)> {
+        self.symbols()
+            .filter(|&(row, column, _)| self.adjacent_numbers(row, column).is_empty())
+            .collect()
+    }
+}
+
+#[test]
+fn test_symbol_stats() {
+    use std::str::FromStr;
+
+    let board = Board::from_str(crate::EXAMPLE).unwrap();
+    let stats = board.symbol_stats();
+    assert_eq!(
+        stats[&'*'],
+        SymbolStats {
+            occurrences: 3,
+            parts: 5,
+            part_sum: 467 + 35 + 617 + 755 + 598
+        }
+    );
+    assert_eq!(stats[&'#'].part_sum, 633);
+    assert_eq!(
+        stats.values().map(|stats| stats.part_sum).sum::<i64>(),
+        4361
+    );
+    assert!(board.isolated_symbols().is_empty());
+
+    let board = Board::from_str("1...&\n.$*..\n.....").unwrap();
+    assert_eq!(board.isolated_symbols(), vec![(0, 4, '&'), (1, 2, '*')]);
+    assert_eq!(board.symbol_stats()[&'&'].parts, 0);
+    assert_eq!(board.symbol_stats()[&'$'].parts, 1);
+}
@@ -10,6 +10,9 @@ struct AoCRunner {
     day: usize,
     #[clap(short, long)]
     input: PathBuf,
+    /// Bag for day 2, e.g. "12 red, 13 green, 14 blue"
+    #[clap(short, long)]
+    bag: Option<day2::Config>,
 }
 
 fn main() -> Result<()> {
@@ -17,6 +20,15 @@ fn main() -> Result<()> {
 
     let input = std::fs::read_to_string(&args.input)?;
 
+    if let Some(bag) = &args.bag {
+        if args.day != 2 {
+            return Err(color_eyre::eyre::eyre!("--bag only applies to day 2"));
+        }
+        let games = day2::try_parse_games(&input)?;
+        print!("{}", day2::feasibility(&games, bag));
+        return Ok(());
+    }
+
     meta::AoC2023::solve_day(args.day, &input).map_err(|e| color_eyre::eyre::eyre!(e))?;
 
     Ok(())